@@ -0,0 +1,182 @@
+//! API calls related to analytics
+//!
+//! [documentation](https://docs.modrinth.com/api/operations/category/analytics)
+
+use super::*;
+use structures::{analytics::*, Decimal, Int, UtcTime};
+use url::Url;
+
+/// Build the URL of the analytics `route` for the given `items` and time range
+fn analytics_url(
+    route: &[&str],
+    items: AnalyticsItems,
+    start: &UtcTime,
+    end: &UtcTime,
+    resolution: Option<Resolution>,
+) -> Result<Url> {
+    let mut segments = vec!["analytics"];
+    segments.extend_from_slice(route);
+    let url = API_BASE_URL.join_all(segments);
+    let mut url = match items {
+        AnalyticsItems::Projects(project_ids) => url.with_query_json("project_ids", project_ids)?,
+        AnalyticsItems::Versions(version_ids) => url.with_query_json("version_ids", version_ids)?,
+    }
+    .with_query("start_date", start.to_rfc3339())
+    .with_query("end_date", end.to_rfc3339());
+    if let Some(resolution) = resolution {
+        url = url.with_query("resolution_minutes", resolution.minutes());
+    }
+    Ok(url)
+}
+
+impl Ferinth<Authenticated> {
+    /**
+    Get the downloads of `items` from `start` to `end`, aggregated by `resolution`
+
+    ```no_run
+    # use ferinth::structures::analytics::{AnalyticsItems, Resolution};
+    # use chrono::{Duration, offset::Utc};
+    # tokio_test::block_on(async {
+    # let modrinth = ferinth::Ferinth::<ferinth::Authenticated>::new(
+    #     env!("CARGO_CRATE_NAME"),
+    #     Some(env!("CARGO_PKG_VERSION")),
+    #     None,
+    #     env!("MODRINTH_TOKEN"),
+    # )?;
+    // Get the daily downloads of every version of the project of ID `XXXXXXXX` over the last week
+    let versions = modrinth.version_list("XXXXXXXX").await?;
+    let version_ids = versions.into_iter().map(|v| v.id).collect::<Vec<_>>();
+    let downloads = modrinth.analytics_downloads(
+        AnalyticsItems::Versions(&version_ids),
+        &(Utc::now() - Duration::weeks(1)),
+        &Utc::now(),
+        Resolution::Day,
+    ).await?;
+    for (version_id, series) in downloads {
+        let total: usize = series.values().sum();
+        println!("{version_id}: {total}");
+    }
+    # Ok::<_, ferinth::Error>(()) }).unwrap()
+    ```
+    */
+    pub async fn analytics_downloads(
+        &self,
+        items: AnalyticsItems<'_>,
+        start: &UtcTime,
+        end: &UtcTime,
+        resolution: Resolution,
+    ) -> Result<AnalyticsData<Int>> {
+        self.client
+            .get(analytics_url(
+                &["downloads"],
+                items,
+                start,
+                end,
+                Some(resolution),
+            )?)
+            .custom_send_json()
+            .await
+    }
+
+    /// Get the page views of the projects of `project_ids` from `start` to `end`, aggregated by `resolution`
+    pub async fn analytics_views(
+        &self,
+        project_ids: &[impl IntoId<ProjectId> + Clone],
+        start: &UtcTime,
+        end: &UtcTime,
+        resolution: Resolution,
+    ) -> Result<AnalyticsData<Int>> {
+        let project_ids = into_ids(project_ids)?;
+        self.client
+            .get(analytics_url(
+                &["views"],
+                AnalyticsItems::Projects(&project_ids),
+                start,
+                end,
+                Some(resolution),
+            )?)
+            .custom_send_json()
+            .await
+    }
+
+    /// Get the playtime in seconds of `items` from `start` to `end`, aggregated by `resolution`
+    pub async fn analytics_playtime(
+        &self,
+        items: AnalyticsItems<'_>,
+        start: &UtcTime,
+        end: &UtcTime,
+        resolution: Resolution,
+    ) -> Result<AnalyticsData<Int>> {
+        self.client
+            .get(analytics_url(
+                &["playtime"],
+                items,
+                start,
+                end,
+                Some(resolution),
+            )?)
+            .custom_send_json()
+            .await
+    }
+
+    /// Get the revenue of the projects of `project_ids` from `start` to `end`, aggregated by `resolution`
+    pub async fn analytics_revenue(
+        &self,
+        project_ids: &[impl IntoId<ProjectId> + Clone],
+        start: &UtcTime,
+        end: &UtcTime,
+        resolution: Resolution,
+    ) -> Result<AnalyticsData<Decimal>> {
+        let project_ids = into_ids(project_ids)?;
+        self.client
+            .get(analytics_url(
+                &["revenue"],
+                AnalyticsItems::Projects(&project_ids),
+                start,
+                end,
+                Some(resolution),
+            )?)
+            .custom_send_json()
+            .await
+    }
+
+    /// Get the downloads of the projects of `project_ids` from `start` to `end`, broken down by country
+    pub async fn analytics_downloads_by_country(
+        &self,
+        project_ids: &[impl IntoId<ProjectId> + Clone],
+        start: &UtcTime,
+        end: &UtcTime,
+    ) -> Result<CountryData> {
+        let project_ids = into_ids(project_ids)?;
+        self.client
+            .get(analytics_url(
+                &["countries", "downloads"],
+                AnalyticsItems::Projects(&project_ids),
+                start,
+                end,
+                None,
+            )?)
+            .custom_send_json()
+            .await
+    }
+
+    /// Get the page views of the projects of `project_ids` from `start` to `end`, broken down by country
+    pub async fn analytics_views_by_country(
+        &self,
+        project_ids: &[impl IntoId<ProjectId> + Clone],
+        start: &UtcTime,
+        end: &UtcTime,
+    ) -> Result<CountryData> {
+        let project_ids = into_ids(project_ids)?;
+        self.client
+            .get(analytics_url(
+                &["countries", "views"],
+                AnalyticsItems::Projects(&project_ids),
+                start,
+                end,
+                None,
+            )?)
+            .custom_send_json()
+            .await
+    }
+}
@@ -3,7 +3,9 @@
 //! [documentation](https://docs.modrinth.com/api-spec/#tag/misc)
 
 use super::*;
-use crate::structures::misc::*;
+use crate::structures::{misc::*, project::ImageFileExt};
+use reqwest::{header::CONTENT_TYPE, Body};
+use url::Url;
 
 impl Ferinth<Authenticated> {
     /**
@@ -21,21 +23,69 @@ impl Ferinth<Authenticated> {
     # )?;
     let report = modrinth.submit_report(&ferinth::structures::misc::ReportSubmission {
         report_type: "other".to_string(),
-        item_id: "XXXXXXXX".to_string(),
-        item_type: ferinth::structures::misc::ReportItemType::User,
+        item: ferinth::structures::misc::ReportItem::User("XXXXXXXX".parse()?),
         body: "This is an example report".to_string(),
     }).await?;
     # Ok::<_, ferinth::Error>(()) }).unwrap()
     ```
     */
     pub async fn submit_report(&self, report: &ReportSubmission) -> Result<Report> {
-        check_id_slug(&[&report.item_id])?;
         self.client
             .post(API_BASE_URL.join_all(vec!["report"]))
             .json(report)
             .custom_send_json()
             .await
     }
+
+    /**
+    Upload `image` of file `ext`ension to be embedded in the Markdown of the given `context`,
+    and return the URL it is hosted at
+
+    ```no_run
+    # use ferinth::structures::{misc::ImageContext, project::ImageFileExt};
+    # tokio_test::block_on(async {
+    # let modrinth = ferinth::Ferinth::<ferinth::Authenticated>::new(
+    #     env!("CARGO_CRATE_NAME"),
+    #     Some(env!("CARGO_PKG_VERSION")),
+    #     None,
+    #     env!("MODRINTH_TOKEN"),
+    # )?;
+    let screenshot = std::fs::read("screenshot.png").unwrap();
+    let url = modrinth.image_upload(
+        &ImageContext::Version("XXXXXXXX".parse()?),
+        screenshot,
+        ImageFileExt::PNG,
+    ).await?;
+    let changelog = format!("![New settings menu]({url})");
+    # Ok::<_, ferinth::Error>(()) }).unwrap()
+    ```
+    */
+    pub async fn image_upload(
+        &self,
+        context: &ImageContext,
+        image: impl Into<Body>,
+        ext: ImageFileExt,
+    ) -> Result<Url> {
+        #[derive(serde::Deserialize)]
+        struct Response {
+            url: Url,
+        }
+        let (context, id_name, id) = context.query();
+        let res: Response = self
+            .client
+            .post(
+                API_BASE_URL
+                    .join_all(vec!["image"])
+                    .with_query("ext", ext)
+                    .with_query("context", context)
+                    .with_query(id_name, id),
+            )
+            .body(image)
+            .header(CONTENT_TYPE, ext.content_type())
+            .custom_send_json()
+            .await?;
+        Ok(res.url)
+    }
 }
 
 impl<T> Ferinth<T> {
@@ -45,7 +95,7 @@ impl<T> Ferinth<T> {
     ## Example
     ```rust
     # tokio_test::block_on(async {
-    # let modrinth = ferinth::Ferinth::default();
+    # let modrinth = ferinth::testing::doc_client();
     let statistics = modrinth.instance_statistics().await?;
     # Ok::<_, ferinth::Error>(()) }).unwrap()
     ```
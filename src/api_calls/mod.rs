@@ -1,6 +1,8 @@
 //! [documentation](https://docs.modrinth.com/api-spec)
 
+pub mod analytics;
 pub mod misc;
+pub mod payout;
 pub mod project;
 pub mod search;
 pub mod tag;
@@ -11,7 +13,7 @@ pub mod version_file;
 
 use crate::{
     request::RequestBuilderCustomSend,
-    structures,
+    structures::{self, id::*, version::HashAlgorithm},
     url_ext::{UrlJoinAll, UrlWithQuery},
     Authenticated, Error, Ferinth, Result, API_BASE_URL,
 };
@@ -36,3 +38,27 @@ pub fn check_sha1_hash<S: AsRef<str>>(inputs: &[S]) -> Result<()> {
     }
     Ok(())
 }
+
+/// Verify that the given `inputs` are SHA512 compliant
+pub fn check_sha512_hash<S: AsRef<str>>(inputs: &[S]) -> Result<()> {
+    for input in inputs {
+        if !lazy_regex::regex_is_match!("^[a-f0-9]{128}$", input.as_ref()) {
+            return Err(Error::InvalidSHA512);
+        }
+    }
+    Ok(())
+}
+
+/// Verify that the given `inputs` are compliant with hashes of `algorithm`
+fn check_hash<S: AsRef<str>>(algorithm: HashAlgorithm, inputs: &[S]) -> Result<()> {
+    match algorithm {
+        HashAlgorithm::SHA1 => check_sha1_hash(inputs),
+        HashAlgorithm::SHA512 => check_sha512_hash(inputs),
+        HashAlgorithm::Other => Err(Error::UnsupportedHashAlgorithm),
+    }
+}
+
+/// Convert all the `inputs` into `I`s, validating them
+pub(crate) fn into_ids<I>(inputs: &[impl IntoId<I> + Clone]) -> Result<Vec<I>> {
+    inputs.iter().cloned().map(IntoId::into_id).collect()
+}
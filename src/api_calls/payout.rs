@@ -0,0 +1,123 @@
+//! API calls related to payouts
+
+use super::*;
+use crate::structures::payout::*;
+
+impl Ferinth<Authenticated> {
+    /**
+    Get the payout history of the current user
+
+    ## Example
+    ```rust
+    # tokio_test::block_on(async {
+    # let modrinth = ferinth::testing::doc_client_authenticated();
+    let payouts = modrinth.payout_list().await?;
+    # Ok::<_, ferinth::Error>(()) }).unwrap()
+    ```
+    */
+    pub async fn payout_list(&self) -> Result<Vec<Payout>> {
+        self.client
+            .get(API_BASE_URL.join_all(vec!["payout"]))
+            .custom_send_json()
+            .await
+    }
+
+    /**
+    Get the available and pending balance of the current user
+
+    ## Example
+    ```rust
+    # tokio_test::block_on(async {
+    # let modrinth = ferinth::testing::doc_client_authenticated();
+    let balance = modrinth.payout_balance().await?;
+    assert!(balance.available >= ferinth::structures::Decimal::ZERO);
+    # Ok::<_, ferinth::Error>(()) }).unwrap()
+    ```
+    */
+    pub async fn payout_balance(&self) -> Result<UserBalance> {
+        self.client
+            .get(API_BASE_URL.join_all(vec!["payout", "balance"]))
+            .custom_send_json()
+            .await
+    }
+
+    /**
+    List the payout methods available in the `country` of the given ISO 3166-1 alpha-2 code,
+    or in the United States if not provided
+
+    ## Example
+    ```rust
+    # tokio_test::block_on(async {
+    # let modrinth = ferinth::testing::doc_client_authenticated();
+    let methods = modrinth.payout_list_methods(Some("GB")).await?;
+    # Ok::<_, ferinth::Error>(()) }).unwrap()
+    ```
+    */
+    pub async fn payout_list_methods(&self, country: Option<&str>) -> Result<Vec<PayoutMethod>> {
+        let mut url = API_BASE_URL.join_all(vec!["payout", "methods"]);
+        if let Some(country) = country {
+            url = url.with_query("country", country);
+        }
+        self.client.get(url).custom_send_json().await
+    }
+
+    /**
+    Request a `withdrawal` from the balance of the current user
+
+    ```no_run
+    # use ferinth::structures::payout::{PayoutMethodType, Withdrawal};
+    # tokio_test::block_on(async {
+    # let modrinth = ferinth::Ferinth::<ferinth::Authenticated>::new(
+    #     env!("CARGO_CRATE_NAME"),
+    #     Some(env!("CARGO_PKG_VERSION")),
+    #     None,
+    #     env!("MODRINTH_TOKEN"),
+    # )?;
+    // Withdraw $10.50 to the user's PayPal account
+    let method = modrinth
+        .payout_list_methods(None)
+        .await?
+        .into_iter()
+        .find(|method| method.method_type == PayoutMethodType::PayPal)
+        .unwrap();
+    modrinth.payout_withdraw(&Withdrawal {
+        amount: ferinth::structures::Decimal::new(1050, 2),
+        method: method.method_type,
+        method_id: method.id,
+    }).await?;
+    # Ok::<_, ferinth::Error>(()) }).unwrap()
+    ```
+    */
+    pub async fn payout_withdraw(&self, withdrawal: &Withdrawal) -> Result<()> {
+        self.client
+            .post(API_BASE_URL.join_all(vec!["payout"]))
+            .json(withdrawal)
+            .custom_send()
+            .await?;
+        Ok(())
+    }
+
+    /**
+    Cancel the pending payout of `payout_id`
+
+    ```no_run
+    # tokio_test::block_on(async {
+    # let modrinth = ferinth::Ferinth::<ferinth::Authenticated>::new(
+    #     env!("CARGO_CRATE_NAME"),
+    #     Some(env!("CARGO_PKG_VERSION")),
+    #     None,
+    #     env!("MODRINTH_TOKEN"),
+    # )?;
+    modrinth.payout_cancel("XXXXXXXX").await?;
+    # Ok::<_, ferinth::Error>(()) }).unwrap()
+    ```
+    */
+    pub async fn payout_cancel(&self, payout_id: impl IntoId<PayoutId>) -> Result<()> {
+        let payout_id = payout_id.into_id()?;
+        self.client
+            .delete(API_BASE_URL.join_all(vec!["payout", payout_id.as_str()]))
+            .custom_send()
+            .await?;
+        Ok(())
+    }
+}
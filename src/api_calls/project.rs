@@ -16,7 +16,7 @@ impl<T> Ferinth<T> {
     ## Example
     ```rust
     # tokio_test::block_on(async {
-    # let modrinth = ferinth::Ferinth::default();
+    # let modrinth = ferinth::testing::doc_client();
     // Get a mod using its project ID
     let sodium = modrinth.project_get("AANobbMI").await?;
     assert_eq!(sodium.title, "Sodium");
@@ -27,10 +27,10 @@ impl<T> Ferinth<T> {
     # Ok::<_, ferinth::Error>(()) }).unwrap()
     ```
     */
-    pub async fn project_get(&self, project_id: &str) -> Result<Project> {
-        check_id_slug(&[project_id])?;
+    pub async fn project_get(&self, project_id: impl IntoId<ProjectRef>) -> Result<Project> {
+        let project_id = project_id.into_id()?;
         self.client
-            .get(API_BASE_URL.join_all(vec!["project", project_id]))
+            .get(API_BASE_URL.join_all(vec!["project", project_id.as_str()]))
             .custom_send_json()
             .await
     }
@@ -41,7 +41,7 @@ impl<T> Ferinth<T> {
     ## Example
     ```rust
     # tokio_test::block_on(async {
-    # let modrinth = ferinth::Ferinth::default();
+    # let modrinth = ferinth::testing::doc_client();
     // You can use both IDs and slugs
     let projects = modrinth.project_get_multiple(&[
         "sodium",
@@ -53,8 +53,11 @@ impl<T> Ferinth<T> {
     # Ok::<_, ferinth::Error>(()) }).unwrap()
     ```
     */
-    pub async fn project_get_multiple(&self, project_ids: &[&str]) -> Result<Vec<Project>> {
-        check_id_slug(project_ids)?;
+    pub async fn project_get_multiple(
+        &self,
+        project_ids: &[impl IntoId<ProjectRef> + Clone],
+    ) -> Result<Vec<Project>> {
+        let project_ids = into_ids(project_ids)?;
         self.client
             .get(
                 API_BASE_URL
@@ -74,7 +77,7 @@ impl<T> Ferinth<T> {
     ## Example
     ```rust
     # tokio_test::block_on(async {
-    # let modrinth = ferinth::Ferinth::default();
+    # let modrinth = ferinth::testing::doc_client();
     let random_projects = modrinth.project_get_random(5).await?;
     // The proper check has been disabled due to the reason mentioned above
     // assert_eq!(random_projects.len(), 5);
@@ -100,21 +103,24 @@ impl<T> Ferinth<T> {
     ## Example
     ```rust
     # tokio_test::block_on(async {
-    # let modrinth = ferinth::Ferinth::default();
+    # let modrinth = ferinth::testing::doc_client();
     let project_id = modrinth.project_check_validity("sodium").await?;
     assert_eq!(project_id, "AANobbMI");
     # Ok::<_, ferinth::Error>(()) }).unwrap()
     ```
     */
-    pub async fn project_check_validity(&self, project_id: &str) -> Result<String> {
+    pub async fn project_check_validity(
+        &self,
+        project_id: impl IntoId<ProjectRef>,
+    ) -> Result<ProjectId> {
         #[derive(serde::Deserialize)]
         struct Response {
-            id: String,
+            id: ProjectId,
         }
-        check_id_slug(&[project_id])?;
+        let project_id = project_id.into_id()?;
         let res: Response = self
             .client
-            .get(API_BASE_URL.join_all(vec!["project", project_id, "check"]))
+            .get(API_BASE_URL.join_all(vec!["project", project_id.as_str(), "check"]))
             .custom_send_json()
             .await?;
         Ok(res.id)
@@ -126,17 +132,20 @@ impl<T> Ferinth<T> {
     ## Example
     ```rust
     # tokio_test::block_on(async {
-    # let modrinth = ferinth::Ferinth::default();
+    # let modrinth = ferinth::testing::doc_client();
     let fabric_api = modrinth.project_get_dependencies("fabric-api").await?;
     // Fabric API should not have any dependencies
     assert!(fabric_api.projects.is_empty());
     # Ok::<_, ferinth::Error>(()) }).unwrap()
     ```
     */
-    pub async fn project_get_dependencies(&self, project_id: &str) -> Result<ProjectDependencies> {
-        check_id_slug(&[project_id])?;
+    pub async fn project_get_dependencies(
+        &self,
+        project_id: impl IntoId<ProjectRef>,
+    ) -> Result<ProjectDependencies> {
+        let project_id = project_id.into_id()?;
         self.client
-            .get(API_BASE_URL.join_all(vec!["project", project_id, "dependencies"]))
+            .get(API_BASE_URL.join_all(vec!["project", project_id.as_str(), "dependencies"]))
             .custom_send_json()
             .await
     }
@@ -144,10 +153,10 @@ impl<T> Ferinth<T> {
 
 impl Ferinth<Authenticated> {
     /// Delete the project of `project_id`
-    pub async fn project_delete(&self, project_id: &str) -> Result<()> {
-        check_id_slug(&[project_id])?;
+    pub async fn project_delete(&self, project_id: impl IntoId<ProjectRef>) -> Result<()> {
+        let project_id = project_id.into_id()?;
         self.client
-            .delete(API_BASE_URL.join_all(vec!["project", project_id]))
+            .delete(API_BASE_URL.join_all(vec!["project", project_id.as_str()]))
             .custom_send()
             .await?;
         Ok(())
@@ -156,10 +165,10 @@ impl Ferinth<Authenticated> {
     /// Bulk edit the projects of `project_ids` with the given `edits`
     pub async fn project_edit_multiple(
         &self,
-        project_ids: &[&str],
+        project_ids: &[impl IntoId<ProjectRef> + Clone],
         edits: EditMultipleProjectsBody,
     ) -> Result<()> {
-        check_id_slug(project_ids)?;
+        into_ids::<ProjectRef>(project_ids)?;
         self.client
             .patch(API_BASE_URL.join_all(vec!["projects"]))
             .json(&edits)
@@ -171,29 +180,29 @@ impl Ferinth<Authenticated> {
     /// Change the icon of the project of `project_id` to `image` with file `ext`ension
     pub async fn project_edit_icon(
         &self,
-        project_id: &str,
+        project_id: impl IntoId<ProjectRef>,
         image: impl Into<Body>,
         ext: ImageFileExt,
     ) -> Result<()> {
-        check_id_slug(&[project_id])?;
+        let project_id = project_id.into_id()?;
         self.client
             .patch(
                 API_BASE_URL
-                    .join_all(vec!["project", project_id, "icon"])
+                    .join_all(vec!["project", project_id.as_str(), "icon"])
                     .with_query("ext", ext),
             )
             .body(image)
-            .header(CONTENT_TYPE, format!("image/{}", ext))
+            .header(CONTENT_TYPE, ext.content_type())
             .custom_send()
             .await?;
         Ok(())
     }
 
     /// Delete the icon of the project of `project_id`
-    pub async fn project_delete_icon(&self, project_id: &str) -> Result<()> {
-        check_id_slug(&[project_id])?;
+    pub async fn project_delete_icon(&self, project_id: impl IntoId<ProjectRef>) -> Result<()> {
+        let project_id = project_id.into_id()?;
         self.client
-            .delete(API_BASE_URL.join_all(vec!["project", project_id, "icon"]))
+            .delete(API_BASE_URL.join_all(vec!["project", project_id.as_str(), "icon"]))
             .custom_send()
             .await?;
         Ok(())
@@ -207,16 +216,16 @@ impl Ferinth<Authenticated> {
     */
     pub async fn project_add_gallery_image<B: Into<Body>>(
         &self,
-        project_id: &str,
+        project_id: impl IntoId<ProjectRef>,
         image: B,
         ext: &ImageFileExt,
         featured: bool,
         title: Option<String>,
         description: Option<String>,
     ) -> Result<()> {
-        check_id_slug(&[project_id])?;
+        let project_id = project_id.into_id()?;
         let mut url = API_BASE_URL
-            .join_all(vec!["project", project_id, "gallery"])
+            .join_all(vec!["project", project_id.as_str(), "gallery"])
             .with_query("ext", ext)
             .with_query("featured", featured);
         if let Some(title) = title {
@@ -228,10 +237,7 @@ impl Ferinth<Authenticated> {
         self.client
             .post(url)
             .body(image)
-            .header(
-                CONTENT_TYPE,
-                HeaderValue::from_str(&format!("image/{}", ext))?,
-            )
+            .header(CONTENT_TYPE, HeaderValue::from_str(&ext.content_type())?)
             .custom_send()
             .await?;
         Ok(())
@@ -240,16 +246,16 @@ impl Ferinth<Authenticated> {
     /// Modify the gallery image of `url` of the project of `project_id`
     pub async fn project_edit_gallery_image<U: IntoUrl>(
         &self,
-        project_id: &str,
+        project_id: impl IntoId<ProjectRef>,
         url: U,
         featured: Option<bool>,
         title: Option<&str>,
         description: Option<&str>,
         ordering: Option<Int>,
     ) -> Result<()> {
-        check_id_slug(&[project_id])?;
+        let project_id = project_id.into_id()?;
         let mut url = API_BASE_URL
-            .join_all(vec!["project", project_id, "gallery"])
+            .join_all(vec!["project", project_id.as_str(), "gallery"])
             .with_query("url", url.into_url()?);
         if let Some(featured) = featured {
             url = url.with_query("featured", featured);
@@ -270,14 +276,14 @@ impl Ferinth<Authenticated> {
     /// Delete the gallery image of `image_url` from the project of `project_id`
     pub async fn project_delete_gallery_image<U: IntoUrl>(
         &self,
-        project_id: &str,
+        project_id: impl IntoId<ProjectRef>,
         image_url: U,
     ) -> Result<()> {
-        check_id_slug(&[project_id])?;
+        let project_id = project_id.into_id()?;
         self.client
             .delete(
                 API_BASE_URL
-                    .join_all(vec!["project", project_id, "gallery"])
+                    .join_all(vec!["project", project_id.as_str(), "gallery"])
                     .with_query("url", image_url.into_url()?),
             )
             .custom_send()
@@ -286,20 +292,20 @@ impl Ferinth<Authenticated> {
     }
 
     /// Follow the project of `project_id`
-    pub async fn project_follow(&self, project_id: &str) -> Result<()> {
-        check_id_slug(&[project_id])?;
+    pub async fn project_follow(&self, project_id: impl IntoId<ProjectRef>) -> Result<()> {
+        let project_id = project_id.into_id()?;
         self.client
-            .post(API_BASE_URL.join_all(vec!["project", project_id, "follow"]))
+            .post(API_BASE_URL.join_all(vec!["project", project_id.as_str(), "follow"]))
             .custom_send()
             .await?;
         Ok(())
     }
 
     /// Unfollow the project of `project_id`
-    pub async fn project_unfollow(&self, project_id: &str) -> Result<()> {
-        check_id_slug(&[project_id])?;
+    pub async fn project_unfollow(&self, project_id: impl IntoId<ProjectRef>) -> Result<()> {
+        let project_id = project_id.into_id()?;
         self.client
-            .delete(API_BASE_URL.join_all(vec!["project", project_id, "follow"]))
+            .delete(API_BASE_URL.join_all(vec!["project", project_id.as_str(), "follow"]))
             .custom_send()
             .await?;
         Ok(())
@@ -327,15 +333,15 @@ impl Ferinth<Authenticated> {
     */
     pub async fn project_schedule(
         &self,
-        project_id: &str,
+        project_id: impl IntoId<ProjectRef>,
         time: &UtcTime,
         status: &RequestedStatus,
     ) -> Result<()> {
-        check_id_slug(&[project_id])?;
+        let project_id = project_id.into_id()?;
         self.client
             .post(
                 API_BASE_URL
-                    .join_all(vec!["project", project_id, "schedule"])
+                    .join_all(vec!["project", project_id.as_str(), "schedule"])
                     .with_query_json("time", time)?
                     .with_query_json("requested_status", status)?,
             )
@@ -1,6 +1,13 @@
 use super::*;
+use futures_util::{stream, Stream};
+use std::{collections::VecDeque, future::Future, time::Duration};
 use structures::{search::*, Int};
 
+/// The maximum number of hits that can be requested in one page
+const SEARCH_PAGE_LIMIT: Int = 100;
+/// How long to wait between requesting pages, to stay within the rate limit
+const SEARCH_PAGE_DELAY: Duration = Duration::from_millis(250);
+
 impl<T> Ferinth<T> {
     /**
     Search for projects using `query` string, with pagination
@@ -13,7 +20,7 @@ impl<T> Ferinth<T> {
     ```rust
     # use ferinth::structures::search::{Sort, Facet};
     # tokio_test::block_on(async {
-    # let modrinth = ferinth::Ferinth::default();
+    # let modrinth = ferinth::testing::doc_client();
     let results = modrinth.search_paged(
         "sodium",
         Sort::Relevance,
@@ -60,7 +67,7 @@ impl<T> Ferinth<T> {
     ```rust
     # use ferinth::structures::search::{Sort, Facet};
     # tokio_test::block_on(async {
-    # let modrinth = ferinth::Ferinth::default();
+    # let modrinth = ferinth::testing::doc_client();
     // When searching for 'sodium' and filtering by NeoForge mods
     let results = modrinth.search(
         "sodium",
@@ -90,4 +97,237 @@ impl<T> Ferinth<T> {
 
         self.client.get(url).custom_send_json().await
     }
+
+    /**
+    Search for projects using a `query` built with [`SearchQuery`]
+
+    ## Example
+    ```rust
+    # use ferinth::structures::search::{Field, SearchQuery, Sort};
+    # tokio_test::block_on(async {
+    # let modrinth = ferinth::testing::doc_client();
+    let query = SearchQuery::new("sodium")
+        .sort(Sort::Downloads)
+        .limit(5)
+        .filter(Field::Categories.equals("fabric").or(Field::Categories.equals("quilt")))
+        .filter(Field::Downloads.gt(1_000_000));
+    let results = modrinth.search_query(&query).await?;
+    assert!(results.hits.len() <= 5);
+    # Ok::<_, ferinth::Error>(()) }).unwrap()
+    ```
+    */
+    pub async fn search_query(&self, query: &SearchQuery) -> Result<Response> {
+        let mut url = API_BASE_URL.join_all(vec!["search"]);
+        for (name, value) in query.query_pairs()? {
+            url = url.with_query(name, value);
+        }
+        self.client.get(url).custom_send_json().await
+    }
+
+    /**
+    Search for projects using `query` string, getting every hit by requesting more pages as needed
+
+    Sort the hits by `sort`, and filter projects using the given `facets`.
+    Stop after `max` hits if provided, or once all the hits have been returned.
+    Pages of up to 100 hits are requested, with a delay between them to stay within the rate limit.
+    If a page fails to load, the error is returned and the stream ends.
+
+    ## Example
+    ```rust
+    # use ferinth::structures::search::{Sort, Facet};
+    # use futures_util::TryStreamExt;
+    # tokio_test::block_on(async {
+    # let modrinth = ferinth::testing::doc_client();
+    // Get the 150 most downloaded Fabric mods
+    let hits = modrinth
+        .search_stream(
+            "",
+            Sort::Downloads,
+            vec![vec![Facet::Categories("fabric".into())]],
+            Some(150),
+        )
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(hits.len(), 150);
+    # Ok::<_, ferinth::Error>(()) }).unwrap()
+    ```
+    */
+    pub fn search_stream(
+        &self,
+        query: impl ToString,
+        sort: Sort,
+        facets: Vec<Vec<Facet>>,
+        max: Option<Int>,
+    ) -> impl Stream<Item = Result<SearchHit>> + '_ {
+        let query = query.to_string();
+        paginate(max, move |limit, offset| {
+            let (query, facets) = (query.clone(), facets.clone());
+            async move { self.search_paged(&query, sort, limit, offset, facets).await }
+        })
+    }
+}
+
+/// Stream up to `max` hits from the pages returned by `fetch_page` for a limit and offset
+fn paginate<F, Fut>(max: Option<Int>, fetch_page: F) -> impl Stream<Item = Result<SearchHit>>
+where
+    F: FnMut(Int, Int) -> Fut,
+    Fut: Future<Output = Result<Response>>,
+{
+    struct State<F> {
+        fetch_page: F,
+        hits: VecDeque<SearchHit>,
+        /// The number of hits that have been requested
+        offset: Int,
+        /// The number of hits that have been returned
+        returned: Int,
+        done: bool,
+    }
+    let max = max.unwrap_or(Int::MAX);
+
+    stream::unfold(
+        State {
+            fetch_page,
+            hits: VecDeque::new(),
+            offset: 0,
+            returned: 0,
+            done: false,
+        },
+        move |mut state| async move {
+            loop {
+                if state.returned >= max {
+                    return None;
+                }
+                if let Some(hit) = state.hits.pop_front() {
+                    state.returned += 1;
+                    return Some((Ok(hit), state));
+                }
+                if state.done {
+                    return None;
+                }
+
+                if state.offset > 0 {
+                    tokio::time::sleep(SEARCH_PAGE_DELAY).await;
+                }
+                let limit = SEARCH_PAGE_LIMIT.min(max - state.returned);
+                match (state.fetch_page)(limit, state.offset).await {
+                    Ok(response) => {
+                        state.offset += response.hits.len();
+                        state.done =
+                            response.hits.is_empty() || state.offset >= response.total_hits;
+                        state.hits.extend(response.hits);
+                    }
+                    Err(err) => {
+                        state.done = true;
+                        return Some((Err(err), state));
+                    }
+                }
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::{StreamExt, TryStreamExt};
+    use std::sync::{Arc, Mutex};
+    use tokio::time::Instant;
+
+    fn hit(index: Int) -> SearchHit {
+        serde_json::from_value(serde_json::json!({
+            "slug": null,
+            "title": format!("Project {index}"),
+            "description": "",
+            "categories": [],
+            "client_side": "required",
+            "server_side": "optional",
+            "project_type": "mod",
+            "downloads": 0,
+            "icon_url": null,
+            "color": null,
+            "thread_id": null,
+            "monetization_status": null,
+            "project_id": "XXXXXXXX",
+            "author": "",
+            "display_categories": [],
+            "versions": [],
+            "follows": 0,
+            "date_created": "2024-01-01T00:00:00Z",
+            "date_modified": "2024-01-01T00:00:00Z",
+            "latest_version": "1.20.1",
+            "license": "MIT",
+            "gallery": [],
+            "featured_gallery": null,
+        }))
+        .unwrap()
+    }
+
+    /// Stream up to `max` of `total` hits, returning the titles and the limits and offsets of the pages requested
+    async fn stream(total: Int, max: Option<Int>) -> Result<(Vec<String>, Vec<(Int, Int)>)> {
+        let requests = Arc::new(Mutex::new(vec![]));
+        let hits = paginate(max, |limit, offset| {
+            requests.lock().unwrap().push((limit, offset));
+            async move {
+                Ok(Response {
+                    hits: (offset..total.min(offset + limit)).map(hit).collect(),
+                    offset,
+                    limit,
+                    total_hits: total,
+                })
+            }
+        })
+        .map_ok(|hit| hit.title)
+        .try_collect()
+        .await?;
+        let requests = requests.lock().unwrap().clone();
+        Ok((hits, requests))
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn pages() -> Result<()> {
+        let start = Instant::now();
+        let (hits, requests) = stream(250, None).await?;
+        assert_eq!(hits.len(), 250);
+        assert_eq!(hits[249], "Project 249");
+        assert_eq!(requests, [(100, 0), (100, 100), (100, 200)]);
+        // There is a delay before each page except the first
+        assert_eq!(start.elapsed(), 2 * SEARCH_PAGE_DELAY);
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn max_hits() -> Result<()> {
+        let (hits, requests) = stream(250, Some(150)).await?;
+        assert_eq!(hits.len(), 150);
+        assert_eq!(requests, [(100, 0), (50, 100)]);
+
+        let (hits, requests) = stream(0, Some(10)).await?;
+        assert!(hits.is_empty());
+        assert_eq!(requests, [(10, 0)]);
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn stops_after_error() {
+        let mut pages = 0;
+        let results = paginate(None, |limit, offset| {
+            pages += 1;
+            async move {
+                match offset {
+                    0 => Ok(Response {
+                        hits: (0..limit).map(hit).collect(),
+                        offset,
+                        limit,
+                        total_hits: 1000,
+                    }),
+                    _ => Err(Error::RateLimitExceeded(1)),
+                }
+            }
+        })
+        .collect::<Vec<_>>()
+        .await;
+        assert_eq!(results.len(), 101);
+        assert!(matches!(results[100], Err(Error::RateLimitExceeded(1))));
+        assert_eq!(pages, 2);
+    }
 }
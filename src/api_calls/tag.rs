@@ -12,7 +12,7 @@ impl<T> Ferinth<T> {
     ## Example
     ```rust
     # tokio_test::block_on(async {
-    # let modrinth = ferinth::Ferinth::default();
+    # let modrinth = ferinth::testing::doc_client();
     let categories = modrinth.tag_list_categories().await?;
     # Ok::<_, ferinth::Error>(()) }).unwrap()
     ```
@@ -30,7 +30,7 @@ impl<T> Ferinth<T> {
     ## Example
     ```rust
     # tokio_test::block_on(async {
-    # let modrinth = ferinth::Ferinth::default();
+    # let modrinth = ferinth::testing::doc_client();
     let loaders = modrinth.tag_list_loaders().await?;
     # Ok::<_, ferinth::Error>(()) }).unwrap()
     ```
@@ -48,7 +48,7 @@ impl<T> Ferinth<T> {
     ## Example
     ```rust
     # tokio_test::block_on(async {
-    # let modrinth = ferinth::Ferinth::default();
+    # let modrinth = ferinth::testing::doc_client();
     let game_versions = modrinth.tag_list_game_versions().await?;
     # Ok::<_, ferinth::Error>(()) }).unwrap()
     ```
@@ -66,7 +66,7 @@ impl<T> Ferinth<T> {
     ## Example
     ```rust
     # tokio_test::block_on(async {
-    # let modrinth = ferinth::Ferinth::default();
+    # let modrinth = ferinth::testing::doc_client();
     let license = modrinth.tag_license_text_and_title("MIT").await?;
     assert_eq!(license.title, "MIT License");
     assert!(license.body.contains("MIT License"));
@@ -86,7 +86,7 @@ impl<T> Ferinth<T> {
     ## Example
     ```rust
     # tokio_test::block_on(async {
-    # let modrinth = ferinth::Ferinth::default();
+    # let modrinth = ferinth::testing::doc_client();
     let donation_platforms = modrinth.tag_list_donation_platforms().await?;
     # Ok::<_, ferinth::Error>(()) }).unwrap()
     ```
@@ -104,7 +104,7 @@ impl<T> Ferinth<T> {
     ## Example
     ```rust
     # tokio_test::block_on(async {
-    # let modrinth = ferinth::Ferinth::default();
+    # let modrinth = ferinth::testing::doc_client();
     let report_types = modrinth.tag_list_report_types().await?;
     # Ok::<_, ferinth::Error>(()) }).unwrap()
     ```
@@ -122,7 +122,7 @@ impl<T> Ferinth<T> {
     ## Example
     ```rust
     # tokio_test::block_on(async {
-    # let modrinth = ferinth::Ferinth::default();
+    # let modrinth = ferinth::testing::doc_client();
     let report_types = modrinth.tag_list_project_types().await?;
     # Ok::<_, ferinth::Error>(()) }).unwrap()
     ```
@@ -140,7 +140,7 @@ impl<T> Ferinth<T> {
     ## Example
     ```rust
     # tokio_test::block_on(async {
-    # let modrinth = ferinth::Ferinth::default();
+    # let modrinth = ferinth::testing::doc_client();
     let report_types = modrinth.tag_list_side_types().await?;
     # Ok::<_, ferinth::Error>(()) }).unwrap()
     ```
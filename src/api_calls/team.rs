@@ -12,16 +12,19 @@ impl<T> Ferinth<T> {
     ## Example
     ```rust
     # tokio_test::block_on(async {
-    # let modrinth = ferinth::Ferinth::default();
+    # let modrinth = ferinth::testing::doc_client();
     let saj_team = modrinth.team_list_project_members("stairautojump").await?;
     assert_eq!(saj_team.len(), 2);
     # Ok::<_, ferinth::Error>(()) }).unwrap()
     ```
     */
-    pub async fn team_list_project_members(&self, project_id: &str) -> Result<Vec<TeamMember>> {
-        check_id_slug(&[project_id])?;
+    pub async fn team_list_project_members(
+        &self,
+        project_id: impl IntoId<ProjectRef>,
+    ) -> Result<Vec<TeamMember>> {
+        let project_id = project_id.into_id()?;
         self.client
-            .get(API_BASE_URL.join_all(vec!["project", project_id, "members"]))
+            .get(API_BASE_URL.join_all(vec!["project", project_id.as_str(), "members"]))
             .custom_send_json()
             .await
     }
@@ -32,16 +35,16 @@ impl<T> Ferinth<T> {
     ## Example
     ```rust
     # tokio_test::block_on(async {
-    # let modrinth = ferinth::Ferinth::default();
+    # let modrinth = ferinth::testing::doc_client();
     let saj_team = modrinth.team_list_members("zftNHDXi").await?;
     assert_eq!(saj_team.len(), 2);
     # Ok::<_, ferinth::Error>(()) }).unwrap()
     ```
     */
-    pub async fn team_list_members(&self, team_id: &str) -> Result<Vec<TeamMember>> {
-        check_id_slug(&[team_id])?;
+    pub async fn team_list_members(&self, team_id: impl IntoId<TeamId>) -> Result<Vec<TeamMember>> {
+        let team_id = team_id.into_id()?;
         self.client
-            .get(API_BASE_URL.join_all(vec!["team", team_id, "members"]))
+            .get(API_BASE_URL.join_all(vec!["team", team_id.as_str(), "members"]))
             .custom_send_json()
             .await
     }
@@ -52,7 +55,7 @@ impl<T> Ferinth<T> {
     ## Example
     ```rust
     # tokio_test::block_on(async {
-    # let modrinth = ferinth::Ferinth::default();
+    # let modrinth = ferinth::testing::doc_client();
     let teams = modrinth.team_multiple_list_members(&[
         "4reLOAKe",
         "1HMZl6Mn",
@@ -65,9 +68,9 @@ impl<T> Ferinth<T> {
     */
     pub async fn team_multiple_list_members(
         &self,
-        team_ids: &[&str],
+        team_ids: &[impl IntoId<TeamId> + Clone],
     ) -> Result<Vec<Vec<TeamMember>>> {
-        check_id_slug(team_ids)?;
+        let team_ids = into_ids(team_ids)?;
         self.client
             .get(
                 API_BASE_URL
@@ -95,14 +98,20 @@ impl Ferinth<Authenticated> {
     # Ok::<_, ferinth::Error>(()) }).unwrap()
     ```
     */
-    pub async fn team_add_user(&self, team_id: &str, user_id: &str) -> Result<()> {
+    pub async fn team_add_user(
+        &self,
+        team_id: impl IntoId<TeamId>,
+        user_id: impl IntoId<UserId>,
+    ) -> Result<()> {
         #[derive(serde::Serialize)]
-        struct Body<'a> {
-            user_id: &'a str,
+        struct Body {
+            user_id: UserId,
         }
+        let team_id = team_id.into_id()?;
+        let user_id = user_id.into_id()?;
 
         self.client
-            .post(API_BASE_URL.join_all(vec!["team", team_id, "members"]))
+            .post(API_BASE_URL.join_all(vec!["team", team_id.as_str(), "members"]))
             .json(&Body { user_id })
             .custom_send()
             .await?;
@@ -124,9 +133,10 @@ impl Ferinth<Authenticated> {
     # Ok::<_, ferinth::Error>(()) }).unwrap()
     ```
     */
-    pub async fn team_join(&self, team_id: &str) -> Result<()> {
+    pub async fn team_join(&self, team_id: impl IntoId<TeamId>) -> Result<()> {
+        let team_id = team_id.into_id()?;
         self.client
-            .post(API_BASE_URL.join_all(vec!["team", team_id, "join"]))
+            .post(API_BASE_URL.join_all(vec!["team", team_id.as_str(), "join"]))
             .custom_send()
             .await?;
         Ok(())
@@ -147,9 +157,20 @@ impl Ferinth<Authenticated> {
     # Ok::<_, ferinth::Error>(()) }).unwrap()
     ```
     */
-    pub async fn team_remove_member(&self, team_id: &str, user_id: &str) -> Result<()> {
+    pub async fn team_remove_member(
+        &self,
+        team_id: impl IntoId<TeamId>,
+        user_id: impl IntoId<UserId>,
+    ) -> Result<()> {
+        let team_id = team_id.into_id()?;
+        let user_id = user_id.into_id()?;
         self.client
-            .delete(API_BASE_URL.join_all(vec!["team", team_id, "members", user_id]))
+            .delete(API_BASE_URL.join_all(vec![
+                "team",
+                team_id.as_str(),
+                "members",
+                user_id.as_str(),
+            ]))
             .custom_send()
             .await?;
         Ok(())
@@ -170,14 +191,20 @@ impl Ferinth<Authenticated> {
     # Ok::<_, ferinth::Error>(()) }).unwrap()
     ```
     */
-    pub async fn team_transfer_ownership(&self, team_id: &str, user_id: &str) -> Result<()> {
+    pub async fn team_transfer_ownership(
+        &self,
+        team_id: impl IntoId<TeamId>,
+        user_id: impl IntoId<UserId>,
+    ) -> Result<()> {
         #[derive(serde::Serialize)]
-        struct Body<'a> {
-            user_id: &'a str,
+        struct Body {
+            user_id: UserId,
         }
+        let team_id = team_id.into_id()?;
+        let user_id = user_id.into_id()?;
 
         self.client
-            .post(API_BASE_URL.join_all(vec!["team", team_id, "owner"]))
+            .post(API_BASE_URL.join_all(vec!["team", team_id.as_str(), "owner"]))
             .json(&Body { user_id })
             .custom_send()
             .await?;
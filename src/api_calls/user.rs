@@ -12,7 +12,7 @@ impl<T> Ferinth<T> {
     ## Example
     ```rust
     # tokio_test::block_on(async {
-    # let modrinth = ferinth::Ferinth::default();
+    # let modrinth = ferinth::testing::doc_client();
     let theRookieCoder = modrinth.user_get("7Azq6eD8").await?;
     assert_eq!(
         theRookieCoder.role,
@@ -21,10 +21,10 @@ impl<T> Ferinth<T> {
     # Ok::<_, ferinth::Error>(()) }).unwrap()
     ```
     */
-    pub async fn user_get(&self, user_id: &str) -> Result<User> {
-        check_id_slug(&[user_id])?;
+    pub async fn user_get(&self, user_id: impl IntoId<UserRef>) -> Result<User> {
+        let user_id = user_id.into_id()?;
         self.client
-            .get(API_BASE_URL.join_all(vec!["user", user_id]))
+            .get(API_BASE_URL.join_all(vec!["user", user_id.as_str()]))
             .custom_send_json()
             .await
     }
@@ -36,14 +36,17 @@ impl<T> Ferinth<T> {
     ```rust
     # use ferinth::structures::user::UserRole;
     # tokio_test::block_on(async {
-    # let modrinth = ferinth::Ferinth::default();
+    # let modrinth = ferinth::testing::doc_client();
     let users = modrinth.user_get_multiple(&["TEZXhE2U", "7Azq6eD8"]).await?;
     assert_eq!(users.len(), 2);
     # Ok::<_, ferinth::Error>(()) }).unwrap()
     ```
     */
-    pub async fn user_get_multiple(&self, user_ids: &[&str]) -> Result<Vec<User>> {
-        check_id_slug(user_ids)?;
+    pub async fn user_get_multiple(
+        &self,
+        user_ids: &[impl IntoId<UserRef> + Clone],
+    ) -> Result<Vec<User>> {
+        let user_ids = into_ids(user_ids)?;
         self.client
             .get(
                 API_BASE_URL
@@ -60,16 +63,16 @@ impl<T> Ferinth<T> {
     ## Example
     ```rust
     # tokio_test::block_on(async {
-    # let modrinth = ferinth::Ferinth::default();
+    # let modrinth = ferinth::testing::doc_client();
     let jellysquid_projects = modrinth.user_list_projects("TEZXhE2U").await?;
     assert_eq!(jellysquid_projects.len(), 4);
     # Ok::<_, ferinth::Error>(()) }).unwrap()
     ```
     */
-    pub async fn user_list_projects(&self, user_id: &str) -> Result<Vec<Project>> {
-        check_id_slug(&[user_id])?;
+    pub async fn user_list_projects(&self, user_id: impl IntoId<UserRef>) -> Result<Vec<Project>> {
+        let user_id = user_id.into_id()?;
         self.client
-            .get(API_BASE_URL.join_all(vec!["user", user_id, "projects"]))
+            .get(API_BASE_URL.join_all(vec!["user", user_id.as_str(), "projects"]))
             .custom_send_json()
             .await
     }
@@ -82,21 +85,19 @@ impl Ferinth<Authenticated> {
     ## Example
     ```rust
     # tokio_test::block_on(async {
-    # let modrinth = ferinth::Ferinth::<ferinth::Authenticated>::new(
-    #     env!("CARGO_CRATE_NAME"),
-    #     Some(env!("CARGO_PKG_VERSION")),
-    #     None,
-    #     env!("MODRINTH_TOKEN"),
-    # )?;
+    # let modrinth = ferinth::testing::doc_client_authenticated();
     # let user_id = modrinth.user_get_current().await?.id;
     let notifications = modrinth.user_list_notifications(&user_id).await?;
     # Ok::<_, ferinth::Error>(()) }).unwrap()
     ```
     */
-    pub async fn user_list_notifications(&self, user_id: &str) -> Result<Vec<Notification>> {
-        check_id_slug(&[user_id])?;
+    pub async fn user_list_notifications(
+        &self,
+        user_id: impl IntoId<UserRef>,
+    ) -> Result<Vec<Notification>> {
+        let user_id = user_id.into_id()?;
         self.client
-            .get(API_BASE_URL.join_all(vec!["user", user_id, "notifications"]))
+            .get(API_BASE_URL.join_all(vec!["user", user_id.as_str(), "notifications"]))
             .custom_send_json()
             .await
     }
@@ -104,24 +105,26 @@ impl Ferinth<Authenticated> {
     /**
     Get the projects that the user of `user_id` has followed
 
+    Only projects can be followed; Modrinth has no route for following organisations in either API version.
+    The organisations that the user is a member of are listed by
+    [`V3::user_list_organizations`](crate::v3::V3::user_list_organizations).
+
     ## Example
     ```rust
     # tokio_test::block_on(async {
-    # let modrinth = ferinth::Ferinth::<ferinth::Authenticated>::new(
-    #     env!("CARGO_CRATE_NAME"),
-    #     Some(env!("CARGO_PKG_VERSION")),
-    #     None,
-    #     env!("MODRINTH_TOKEN"),
-    # )?;
+    # let modrinth = ferinth::testing::doc_client_authenticated();
     # let user_id = modrinth.user_get_current().await?.id;
     let projects = modrinth.user_list_followed_projects(&user_id).await?;
     # Ok::<_, ferinth::Error>(()) }).unwrap()
     ```
     */
-    pub async fn user_list_followed_projects(&self, user_id: &str) -> Result<Vec<Project>> {
-        check_id_slug(&[user_id])?;
+    pub async fn user_list_followed_projects(
+        &self,
+        user_id: impl IntoId<UserRef>,
+    ) -> Result<Vec<Project>> {
+        let user_id = user_id.into_id()?;
         self.client
-            .get(API_BASE_URL.join_all(vec!["user", user_id, "follows"]))
+            .get(API_BASE_URL.join_all(vec!["user", user_id.as_str(), "follows"]))
             .custom_send_json()
             .await
     }
@@ -141,10 +144,10 @@ impl Ferinth<Authenticated> {
     # Ok::<_, ferinth::Error>(()) }).unwrap()
     ```
     */
-    pub async fn user_delete(&self, user_id: &str) -> Result<()> {
-        check_id_slug(&[user_id])?;
+    pub async fn user_delete(&self, user_id: impl IntoId<UserRef>) -> Result<()> {
+        let user_id = user_id.into_id()?;
         self.client
-            .delete(API_BASE_URL.join_all(vec!["user", user_id]))
+            .delete(API_BASE_URL.join_all(vec!["user", user_id.as_str()]))
             .custom_send()
             .await?;
         Ok(())
@@ -156,21 +159,77 @@ impl Ferinth<Authenticated> {
     ## Example
     ```rust
     # tokio_test::block_on(async {
+    # let modrinth = ferinth::testing::doc_client_authenticated();
+    let current_user = modrinth.user_get_current().await?;
+    // The email should be visible as we are authorised
+    assert!(current_user.email.is_some());
+    # Ok::<_, ferinth::Error>(()) }).unwrap()
+    ```
+    */
+    pub async fn user_get_current(&self) -> Result<User> {
+        self.client
+            .get(API_BASE_URL.join_all(vec!["user"]))
+            .custom_send_json()
+            .await
+    }
+
+    /**
+    List the active sessions of the current user
+
+    ## Example
+    ```rust
+    # tokio_test::block_on(async {
+    # let modrinth = ferinth::testing::doc_client_authenticated();
+    let sessions = modrinth.user_list_sessions().await?;
+    # Ok::<_, ferinth::Error>(()) }).unwrap()
+    ```
+    */
+    pub async fn user_list_sessions(&self) -> Result<Vec<Session>> {
+        self.client
+            .get(API_BASE_URL.join_all(vec!["session", "list"]))
+            .custom_send_json()
+            .await
+    }
+
+    /**
+    Revoke the session of `session_id`, logging it out
+
+    ```no_run
+    # tokio_test::block_on(async {
     # let modrinth = ferinth::Ferinth::<ferinth::Authenticated>::new(
     #     env!("CARGO_CRATE_NAME"),
     #     Some(env!("CARGO_PKG_VERSION")),
     #     None,
     #     env!("MODRINTH_TOKEN"),
     # )?;
-    let current_user = modrinth.user_get_current().await?;
-    // The email should be visible as we are authorised
-    assert!(current_user.email.is_some());
+    // Revoke every session that has not been used in the last 30 days
+    let cutoff = chrono::offset::Utc::now() - chrono::Duration::days(30);
+    for session in modrinth.user_list_sessions().await? {
+        if !session.current && session.last_login < cutoff {
+            modrinth.user_revoke_session(&session.id).await?;
+        }
+    }
     # Ok::<_, ferinth::Error>(()) }).unwrap()
     ```
     */
-    pub async fn user_get_current(&self) -> Result<User> {
+    pub async fn user_revoke_session(&self, session_id: impl IntoId<SessionId>) -> Result<()> {
+        let session_id = session_id.into_id()?;
         self.client
-            .get(API_BASE_URL.join_all(vec!["user"]))
+            .delete(API_BASE_URL.join_all(vec!["session", session_id.as_str()]))
+            .custom_send()
+            .await?;
+        Ok(())
+    }
+
+    /**
+    Refresh the session used to authorise this request, extending its expiry
+
+    The new session token is available in [`Session::session`],
+    and has to be used for subsequent requests as the old one is revoked.
+    */
+    pub async fn user_refresh_session(&self) -> Result<Session> {
+        self.client
+            .post(API_BASE_URL.join_all(vec!["session", "refresh"]))
             .custom_send_json()
             .await
     }
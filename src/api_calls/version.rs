@@ -21,10 +21,10 @@ impl Ferinth<Authenticated> {
     # Ok::<_, ferinth::Error>(()) }).unwrap()
     ```
     */
-    pub async fn version_delete(&self, version_id: &str) -> Result<()> {
-        check_id_slug(&[version_id])?;
+    pub async fn version_delete(&self, version_id: impl IntoId<VersionId>) -> Result<()> {
+        let version_id = version_id.into_id()?;
         self.client
-            .delete(API_BASE_URL.join_all(vec!["version", version_id]))
+            .delete(API_BASE_URL.join_all(vec!["version", version_id.as_str()]))
             .custom_send()
             .await?;
         Ok(())
@@ -54,15 +54,15 @@ impl Ferinth<Authenticated> {
     */
     pub async fn version_schedule(
         &self,
-        version_id: &str,
+        version_id: impl IntoId<VersionId>,
         time: &UtcTime,
         status: &RequestedStatus,
     ) -> Result<()> {
-        check_id_slug(&[version_id])?;
+        let version_id = version_id.into_id()?;
         self.client
             .post(
                 API_BASE_URL
-                    .join_all(vec!["version", version_id, "schedule"])
+                    .join_all(vec!["version", version_id.as_str(), "schedule"])
                     .with_query_json("time", time)?
                     .with_query_json("requested_status", status)?,
             )
@@ -79,16 +79,16 @@ impl<T> Ferinth<T> {
     ## Example
     ```rust
     # tokio_test::block_on(async {
-    # let modrinth = ferinth::Ferinth::default();
+    # let modrinth = ferinth::testing::doc_client();
     let sodium_versions = modrinth.version_list("AANobbMI").await?;
     sodium_versions.iter().for_each(|v| assert_eq!(v.project_id, "AANobbMI"));
     # Ok::<_, ferinth::Error>(()) }).unwrap()
     ```
     */
-    pub async fn version_list(&self, project_id: &str) -> Result<Vec<Version>> {
-        check_id_slug(&[project_id])?;
+    pub async fn version_list(&self, project_id: impl IntoId<ProjectRef>) -> Result<Vec<Version>> {
+        let project_id = project_id.into_id()?;
         self.client
-            .get(API_BASE_URL.join_all(vec!["project", project_id, "version"]))
+            .get(API_BASE_URL.join_all(vec!["project", project_id.as_str(), "version"]))
             .custom_send_json()
             .await
     }
@@ -100,7 +100,7 @@ impl<T> Ferinth<T> {
     ## Example
     ```rust
     # tokio_test::block_on(async {
-    # let modrinth = ferinth::Ferinth::default();
+    # let modrinth = ferinth::testing::doc_client();
     let sodium_forge_versions = modrinth.version_list_filtered(
         "AANobbMI",
         Some(&["forge"]),
@@ -114,13 +114,13 @@ impl<T> Ferinth<T> {
     */
     pub async fn version_list_filtered(
         &self,
-        project_id: &str,
+        project_id: impl IntoId<ProjectRef>,
         loaders: Option<&[&str]>,
         game_versions: Option<&[&str]>,
         featured: Option<bool>,
     ) -> Result<Vec<Version>> {
-        check_id_slug(&[project_id])?;
-        let mut url = API_BASE_URL.join_all(vec!["project", project_id, "version"]);
+        let project_id = project_id.into_id()?;
+        let mut url = API_BASE_URL.join_all(vec!["project", project_id.as_str(), "version"]);
         if let Some(loaders) = loaders {
             url = url.with_query_json("loaders", loaders)?;
         }
@@ -139,16 +139,16 @@ impl<T> Ferinth<T> {
     ## Example
     ```rust
     # tokio_test::block_on(async {
-    # let modrinth = ferinth::Ferinth::default();
+    # let modrinth = ferinth::testing::doc_client();
     let sodium_version = modrinth.version_get("xuWxRZPd").await?;
     assert_eq!(sodium_version.project_id, "AANobbMI");
     # Ok::<_, ferinth::Error>(()) }).unwrap()
     ```
     */
-    pub async fn version_get(&self, version_id: &str) -> Result<Version> {
-        check_id_slug(&[version_id])?;
+    pub async fn version_get(&self, version_id: impl IntoId<VersionId>) -> Result<Version> {
+        let version_id = version_id.into_id()?;
         self.client
-            .get(API_BASE_URL.join_all(vec!["version", version_id]))
+            .get(API_BASE_URL.join_all(vec!["version", version_id.as_str()]))
             .custom_send_json()
             .await
     }
@@ -159,16 +159,20 @@ impl<T> Ferinth<T> {
     ## Example
     ```rust
     # tokio_test::block_on(async {
-    # let modrinth = ferinth::Ferinth::default();
+    # let modrinth = ferinth::testing::doc_client();
     let version = modrinth.version_get_from_number("sodium", "mc1.17.1-0.3.2").await?;
     assert_eq!(version.id, "xuWxRZPd");
     # Ok::<_, ferinth::Error>(()) }).unwrap()
     ```
     */
-    pub async fn version_get_from_number(&self, project_id: &str, number: &str) -> Result<Version> {
-        check_id_slug(&[project_id])?;
+    pub async fn version_get_from_number(
+        &self,
+        project_id: impl IntoId<ProjectRef>,
+        number: &str,
+    ) -> Result<Version> {
+        let project_id = project_id.into_id()?;
         self.client
-            .get(API_BASE_URL.join_all(vec!["project", project_id, "version", number]))
+            .get(API_BASE_URL.join_all(vec!["project", project_id.as_str(), "version", number]))
             .custom_send_json()
             .await
     }
@@ -179,7 +183,7 @@ impl<T> Ferinth<T> {
     ## Example
     ```rust
     # tokio_test::block_on(async {
-    # let modrinth = ferinth::Ferinth::default();
+    # let modrinth = ferinth::testing::doc_client();
     let versions = modrinth.version_get_multiple(&[
         "sxWTUZpD",
         "mgPpe4NY",
@@ -188,8 +192,11 @@ impl<T> Ferinth<T> {
     # Ok::<_, ferinth::Error>(()) }).unwrap()
     ```
     */
-    pub async fn version_get_multiple(&self, version_ids: &[&str]) -> Result<Vec<Version>> {
-        check_id_slug(version_ids)?;
+    pub async fn version_get_multiple(
+        &self,
+        version_ids: &[impl IntoId<VersionId> + Clone],
+    ) -> Result<Vec<Version>> {
+        let version_ids = into_ids(version_ids)?;
         self.client
             .get(
                 API_BASE_URL
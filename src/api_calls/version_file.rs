@@ -1,12 +1,14 @@
 use super::*;
-use crate::structures::version::*;
+use crate::{hashing::hash_path, structures::version::*};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
 
 impl Ferinth<Authenticated> {
     /**
-    Delete the version file with the `hash`.
-    Only supports SHA1 hashes for now.
+    Delete the version file with the `hash` computed using `algorithm`.
     Optionally specify the version ID to delete the version file from, if multiple files of the same hash exist.
 
     ```no_run
@@ -17,19 +19,32 @@ impl Ferinth<Authenticated> {
     #     None,
     #     env!("MODRINTH_TOKEN"),
     # )?;
-    modrinth.version_file_delete_from_hash("795d4c12bffdb1b21eed5ff87c07ce5ca3c0dcbf", None).await?;
+    modrinth.version_file_delete_from_hash(
+        "795d4c12bffdb1b21eed5ff87c07ce5ca3c0dcbf",
+        ferinth::structures::version::HashAlgorithm::SHA1,
+        None::<&str>,
+    ).await?;
+    // Delete the file from one version only
+    modrinth.version_file_delete_from_hash(
+        "795d4c12bffdb1b21eed5ff87c07ce5ca3c0dcbf",
+        ferinth::structures::version::HashAlgorithm::SHA1,
+        Some("XXXXXXXX"),
+    ).await?;
     # Ok::<_, ferinth::Error>(()) }).unwrap()
     ```
     */
     pub async fn version_file_delete_from_hash(
         &self,
         hash: &str,
-        version_id: Option<&str>,
+        algorithm: HashAlgorithm,
+        version_id: Option<impl IntoId<VersionId>>,
     ) -> Result<()> {
-        check_sha1_hash(&[hash])?;
-        let mut url = API_BASE_URL.join_all(vec!["version_file", hash]);
+        check_hash(algorithm, &[hash])?;
+        let version_id = version_id.map(IntoId::into_id).transpose()?;
+        let mut url = API_BASE_URL
+            .join_all(vec!["version_file", hash])
+            .with_query("algorithm", algorithm);
         if let Some(version_id) = version_id {
-            check_id_slug(&[version_id])?;
             url = url.with_query("version_id", version_id);
         }
         self.client.delete(url).custom_send().await?;
@@ -39,42 +54,54 @@ impl Ferinth<Authenticated> {
 
 impl<T> Ferinth<T> {
     /**
-    Get the version of the version file with `hash`.
-    Only supports SHA1 hashes for now.
+    Get the version of the version file with the `hash` computed using `algorithm`
 
     ## Example
     ```rust
+    # use ferinth::structures::version::HashAlgorithm;
     # tokio_test::block_on(async {
-    # let modrinth = ferinth::Ferinth::default();
+    # let modrinth = ferinth::testing::doc_client();
     // If a mod file has the hash `795d4c12bffdb1b21eed5ff87c07ce5ca3c0dcbf`, we can get the version it belongs to
-    let sodium_version = modrinth.version_get_from_hash("795d4c12bffdb1b21eed5ff87c07ce5ca3c0dcbf").await?;
+    let sodium_version = modrinth.version_get_from_hash(
+        "795d4c12bffdb1b21eed5ff87c07ce5ca3c0dcbf",
+        HashAlgorithm::SHA1,
+    ).await?;
     assert_eq!(sodium_version.project_id, "AANobbMI");
     # Ok::<_, ferinth::Error>(()) }).unwrap()
     ```
     */
-    pub async fn version_get_from_hash(&self, hash: &str) -> Result<Version> {
-        check_sha1_hash(&[hash])?;
+    pub async fn version_get_from_hash(
+        &self,
+        hash: &str,
+        algorithm: HashAlgorithm,
+    ) -> Result<Version> {
+        check_hash(algorithm, &[hash])?;
         self.client
-            .get(API_BASE_URL.join_all(vec!["version_file", hash]))
+            .get(
+                API_BASE_URL
+                    .join_all(vec!["version_file", hash])
+                    .with_query("algorithm", algorithm),
+            )
             .custom_send_json()
             .await
     }
 
     /**
-    Get the versions of the version files with `hashes`, only supports SHA1 hashes for now
+    Get the versions of the version files with `hashes` computed using `algorithm`
 
     Returns a map where the keys are the hashes given.
 
     ## Example
     ```rust
+    # use ferinth::structures::version::HashAlgorithm;
     # tokio_test::block_on(async {
-    # let modrinth = ferinth::Ferinth::default();
+    # let modrinth = ferinth::testing::doc_client();
     let sodium_hash = "795d4c12bffdb1b21eed5ff87c07ce5ca3c0dcbf";
     let snwylvspls_hash = "994ee99d172a5950a51ec2d08c158d270722d871";
     let versions = modrinth.version_get_from_multiple_hashes(vec![
         sodium_hash.into(),
         snwylvspls_hash.into(),
-    ]).await?;
+    ], HashAlgorithm::SHA1).await?;
     assert_eq!(versions[sodium_hash].project_id, "AANobbMI");
     assert_eq!(versions[snwylvspls_hash].project_id, "of7wIinq");
     # Ok::<_, ferinth::Error>(()) }).unwrap()
@@ -83,6 +110,7 @@ impl<T> Ferinth<T> {
     pub async fn version_get_from_multiple_hashes(
         &self,
         hashes: Vec<String>,
+        algorithm: HashAlgorithm,
     ) -> Result<HashMap<String, Version>> {
         #[derive(Deserialize, Serialize, Debug, Clone)]
         pub struct HashesBody {
@@ -90,53 +118,110 @@ impl<T> Ferinth<T> {
             pub algorithm: HashAlgorithm,
         }
 
-        check_sha1_hash(&hashes)?;
+        check_hash(algorithm, &hashes)?;
         self.client
             .post(API_BASE_URL.join_all(vec!["version_files"]))
-            .json(&HashesBody {
-                hashes,
-                algorithm: HashAlgorithm::SHA1,
-            })
+            .json(&HashesBody { hashes, algorithm })
             .custom_send_json()
             .await
     }
 
-    /// Get the latest version for the project of the version file with `hash` based on some `filters`.
-    /// Only supports SHA1 hashes for now.
+    /// Get the latest version for the project of the version file with the `hash` computed using `algorithm`,
+    /// based on some `filters`
     pub async fn version_get_latest_from_hash(
         &self,
         hash: &str,
+        algorithm: HashAlgorithm,
         filters: &LatestVersionBody,
     ) -> Result<Version> {
-        check_sha1_hash(&[hash])?;
+        check_hash(algorithm, &[hash])?;
         self.client
             .post(
                 API_BASE_URL
                     .join_all(vec!["version_file", hash, "update"])
-                    .with_query_json("algorithm", HashAlgorithm::SHA1)?,
+                    .with_query("algorithm", algorithm),
             )
             .json(filters)
             .custom_send_json()
             .await
     }
 
-    /// Get the latest versions of the projects of the version files with hashes based on some `filters`.
-    /// Only supports SHA1 hashes for now.
+    /// Get the latest versions of the projects of the version files with `hashes` computed using `algorithm`,
+    /// based on some `filters`
     pub async fn version_get_latest_from_multiple_hashes(
         &self,
         hashes: Vec<String>,
+        algorithm: HashAlgorithm,
         filters: LatestVersionBody,
     ) -> Result<HashMap<String, Version>> {
-        check_sha1_hash(&hashes)?;
+        check_hash(algorithm, &hashes)?;
         self.client
             .post(API_BASE_URL.join_all(vec!["version_files", "update"]))
             .json(&LatestVersionsBody {
                 hashes,
-                algorithm: HashAlgorithm::SHA1,
+                algorithm,
                 loaders: filters.loaders,
                 game_versions: filters.game_versions,
             })
             .custom_send_json()
             .await
     }
+
+    /**
+    Hash the file at `path` and get the version it belongs to
+
+    ## Example
+    ```no_run
+    # tokio_test::block_on(async {
+    # let modrinth = ferinth::testing::doc_client();
+    let version = modrinth.version_get_from_file("mods/sodium.jar").await?;
+    # Ok::<_, ferinth::Error>(()) }).unwrap()
+    ```
+    */
+    pub async fn version_get_from_file(&self, path: impl AsRef<Path>) -> Result<Version> {
+        let hashes = hash_path(path).await?;
+        self.version_get_from_hash(&hashes.sha1, HashAlgorithm::SHA1)
+            .await
+    }
+
+    /**
+    Hash the files at `paths` and get the versions they belong to
+
+    Returns a map where the keys are the paths given.
+    Files that are not known to Modrinth will not be in the map.
+
+    ## Example
+    ```no_run
+    # tokio_test::block_on(async {
+    # let modrinth = ferinth::testing::doc_client();
+    let mut paths = vec![];
+    for entry in std::fs::read_dir("mods")? {
+        paths.push(entry?.path());
+    }
+    let versions = modrinth.version_get_from_files(&paths).await?;
+    for path in paths.iter().filter(|path| !versions.contains_key(*path)) {
+        println!("{} is not on Modrinth", path.display());
+    }
+    # Ok::<_, ferinth::Error>(()) }).unwrap()
+    ```
+    */
+    pub async fn version_get_from_files(
+        &self,
+        paths: &[impl AsRef<Path>],
+    ) -> Result<HashMap<PathBuf, Version>> {
+        let mut hashes = Vec::with_capacity(paths.len());
+        for path in paths {
+            hashes.push((path.as_ref().to_owned(), hash_path(path).await?.sha1));
+        }
+        let versions = self
+            .version_get_from_multiple_hashes(
+                hashes.iter().map(|(_, hash)| hash.clone()).collect(),
+                HashAlgorithm::SHA1,
+            )
+            .await?;
+        Ok(hashes
+            .into_iter()
+            .filter_map(|(path, hash)| Some((path, versions.get(&hash)?.clone())))
+            .collect())
+    }
 }
@@ -0,0 +1,860 @@
+//! Cache the responses of API calls in memory, and optionally on disk
+
+use crate::{request::RequestBuilder, structures::UtcTime, Error, Ferinth, Result};
+use lru::LruCache;
+use reqwest::{
+    header::{HeaderMap, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED},
+    Method, StatusCode,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    cell::Cell,
+    collections::HashMap,
+    future::Future,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+use tokio::fs;
+use url::Url;
+
+/// The groups of API calls that can be given different cache lifetimes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endpoint {
+    /// Tag lists, such as loaders, game versions, and categories
+    Tag,
+    Project,
+    /// Versions, including those looked up by their files' hashes
+    Version,
+    User,
+    Team,
+    Search,
+    /// All other API calls
+    Other,
+}
+
+impl Endpoint {
+    /// The endpoint that a `method` request to `url` belongs to, or `None` if its responses should never be cached
+    pub(crate) fn from_request(method: &Method, url: &Url) -> Option<Self> {
+        match *method {
+            Method::GET => Self::from_url(url),
+            // Looking up versions by the hashes of their files is a `POST` request
+            Method::POST => match url.path_segments()?.skip(1).collect::<Vec<_>>()[..] {
+                ["version_files", ..] | ["version_file", _, "update"] => Some(Self::Version),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// The endpoint that `url` belongs to, or `None` if its responses should never be cached
+    fn from_url(url: &Url) -> Option<Self> {
+        // Skip the API version segment
+        let route = url.path_segments()?.nth(1)?;
+        Some(match route {
+            "tag" => Self::Tag,
+            "project" | "projects" => Self::Project,
+            "version" | "versions" | "version_file" => Self::Version,
+            "user" | "users" => Self::User,
+            "team" | "teams" => Self::Team,
+            "search" => Self::Search,
+            // Random projects are different every time
+            "projects_random" => return None,
+            _ => Self::Other,
+        })
+    }
+
+    /// How long responses are used for without revalidating them, unless configured otherwise
+    pub fn default_ttl(self) -> Duration {
+        match self {
+            Self::Tag => Duration::from_secs(24 * 60 * 60),
+            Self::Search => Duration::from_secs(60),
+            _ => Duration::from_secs(10 * 60),
+        }
+    }
+}
+
+/// How an API call uses the cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CachePolicy {
+    /// Use cached responses until their TTL expires, then revalidate them
+    #[default]
+    Use,
+    /// Ignore cached responses and fetch new ones, caching them
+    Refresh,
+    /// Neither use nor store cached responses
+    Bypass,
+}
+
+/// Whether API calls contact the server or are answered from the cache
+///
+/// Expired responses are only used for API calls made in [`track_freshness`], which reports that they are stale,
+/// or with [`Ferinth::allow_untracked_stale`]. Otherwise [`Error::Stale`] is returned instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OfflineMode {
+    /// Always contact the server when a cached response is missing or expired
+    #[default]
+    Online,
+    /// Contact the server, but if it cannot be reached, use cached responses even if they have expired
+    Fallback,
+    /// Never contact the server, and use cached responses even if they have expired
+    Always,
+}
+
+/// Whether the responses of API calls were up to date, as returned by [`track_freshness`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Freshness {
+    /// All responses were received from the server or were within their TTL
+    Fresh,
+    /// Some responses were expired cached responses used because of the [`OfflineMode`],
+    /// the oldest of which was received at `since`
+    Stale { since: UtcTime },
+}
+
+impl Freshness {
+    pub fn is_stale(self) -> bool {
+        matches!(self, Self::Stale { .. })
+    }
+}
+
+tokio::task_local! {
+    /// When the oldest stale response used in the current [`track_freshness`] call was received
+    static STALE_SINCE: Cell<Option<UtcTime>>;
+}
+
+/**
+Run `future`, and return its output along with whether the responses of the API calls it made were up to date
+
+Only API calls made in `future` itself are tracked, not those made in tasks it spawns,
+which return [`Error::Stale`] instead of stale responses.
+
+## Example
+```no_run
+# use ferinth::cache::{track_freshness, Cache, OfflineMode};
+# tokio_test::block_on(async {
+let modrinth = ferinth::Ferinth::default()
+    .with_cache(Cache::new(256).disk("cache/modrinth"))
+    .offline_mode(OfflineMode::Fallback);
+let (sodium, freshness) = track_freshness(modrinth.project_get("sodium")).await;
+let sodium = sodium?;
+if let ferinth::cache::Freshness::Stale { since } = freshness {
+    println!("Modrinth is unreachable, showing {} as of {since}", sodium.title);
+}
+# Ok::<_, ferinth::Error>(()) }).unwrap()
+```
+*/
+pub async fn track_freshness<F: Future>(future: F) -> (F::Output, Freshness) {
+    STALE_SINCE
+        .scope(Cell::new(None), async {
+            let output = future.await;
+            let freshness = match STALE_SINCE.with(Cell::get) {
+                Some(since) => Freshness::Stale { since },
+                None => Freshness::Fresh,
+            };
+            (output, freshness)
+        })
+        .await
+}
+
+/// Record that `entry` was used although it is stale and return its body,
+/// or return [`Error::Stale`] if its staleness is not being tracked and `untracked` stale responses are not allowed
+fn use_stale(entry: Entry, untracked: bool) -> Result<String> {
+    let tracked = STALE_SINCE
+        .try_with(|since| {
+            since.set(Some(
+                since.get().map_or(entry.stored, |s| s.min(entry.stored)),
+            ));
+        })
+        .is_ok();
+    if tracked || untracked {
+        Ok(entry.body)
+    } else {
+        Err(Error::Stale {
+            url: entry.url,
+            since: entry.stored,
+        })
+    }
+}
+
+/// A cached response
+#[derive(Deserialize, Serialize, Debug, Clone)]
+struct Entry {
+    url: Url,
+    /// The JSON body of the request, for lookups made with `POST`
+    #[serde(default)]
+    request: Option<String>,
+    /// A digest of the authorisation token the request was sent with
+    #[serde(default)]
+    auth: Option<String>,
+    body: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    /// When the response was received or last revalidated
+    stored: UtcTime,
+}
+
+impl Entry {
+    fn age(&self) -> Duration {
+        (chrono::Utc::now() - self.stored)
+            .to_std()
+            .unwrap_or_default()
+    }
+}
+
+/**
+A cache of API responses, with an in-memory LRU and an optional on-disk store
+
+Responses are used without contacting the server until they are older than the TTL of their [`Endpoint`].
+After that they are revalidated with `If-None-Match` and `If-Modified-Since` if the server sent an `ETag` or `Last-Modified` header,
+so unchanged responses are not downloaded again.
+Only `GET` requests and lookups of versions by file hashes are cached,
+separately for each authorisation token, and successful `POST`, `PATCH`, and `DELETE` requests remove all cached responses except tags.
+
+With an [`OfflineMode`], expired responses are used when the server cannot or should not be contacted,
+which [`track_freshness`] reports, and [`Error::Offline`] is returned for responses that are not cached.
+
+## Example
+```no_run
+# use ferinth::cache::{Cache, CachePolicy, Endpoint};
+# use std::time::Duration;
+# tokio_test::block_on(async {
+let modrinth = ferinth::Ferinth::default().with_cache(
+    Cache::new(256)
+        .disk("cache/modrinth")
+        .ttl(Endpoint::Project, Duration::from_secs(60 * 60)),
+);
+// Only the first call contacts the server
+let loaders = modrinth.tag_list_loaders().await?;
+let loaders = modrinth.tag_list_loaders().await?;
+
+// Skip the cache for a single call
+let sodium = modrinth.cache_policy(CachePolicy::Bypass).project_get("sodium").await?;
+# Ok::<_, ferinth::Error>(()) }).unwrap()
+```
+*/
+#[derive(Debug)]
+pub struct Cache {
+    memory: Mutex<LruCache<String, Entry>>,
+    disk: Option<PathBuf>,
+    ttls: HashMap<Endpoint, Duration>,
+}
+
+impl Cache {
+    /// Create a cache that keeps up to `capacity` responses in memory
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            memory: Mutex::new(LruCache::new(
+                NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN),
+            )),
+            disk: None,
+            ttls: HashMap::new(),
+        }
+    }
+
+    /// Also store responses in the directory `dir`, so that they persist between runs
+    pub fn disk(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.disk = Some(dir.into());
+        self
+    }
+
+    /// Use responses of `endpoint` for `ttl` before revalidating them
+    pub fn ttl(mut self, endpoint: Endpoint, ttl: Duration) -> Self {
+        self.ttls.insert(endpoint, ttl);
+        self
+    }
+
+    /// Remove the cached responses of `url`
+    pub async fn invalidate(&self, url: &Url) -> Result<()> {
+        self.invalidate_where(|entry| entry == url).await
+    }
+
+    /// Remove the cached responses of `endpoint`
+    pub async fn invalidate_endpoint(&self, endpoint: Endpoint) -> Result<()> {
+        self.invalidate_where(|url| Endpoint::from_url(url) == Some(endpoint))
+            .await
+    }
+
+    /// Remove all cached responses
+    pub async fn clear(&self) -> Result<()> {
+        self.invalidate_where(|_| true).await
+    }
+
+    /// Remove the cached responses that a successful `POST`, `PATCH`, or `DELETE` request may have made outdated,
+    /// which is all of them except tags, as projects include their versions, members, and other related data.
+    /// Failing to remove them from disk is not an error, as the request itself succeeded.
+    pub(crate) async fn invalidate_mutated(&self) {
+        let _ = self
+            .invalidate_where(|url| Endpoint::from_url(url) != Some(Endpoint::Tag))
+            .await;
+    }
+
+    async fn invalidate_where(&self, matches: impl Fn(&Url) -> bool) -> Result<()> {
+        {
+            let mut memory = self.memory();
+            let keys = memory
+                .iter()
+                .filter(|(_, entry)| matches(&entry.url))
+                .map(|(key, _)| key.clone())
+                .collect::<Vec<_>>();
+            for key in keys {
+                memory.pop(&key);
+            }
+        }
+
+        let Some(dir) = &self.disk else {
+            return Ok(());
+        };
+        let mut files = match fs::read_dir(dir).await {
+            Ok(files) => files,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(err.into()),
+        };
+        while let Some(file) = files.next_entry().await? {
+            let path = file.path();
+            // Leave files that are not cached responses alone
+            if path.extension().is_none_or(|ext| ext != "json")
+                || !file.file_type().await?.is_file()
+            {
+                continue;
+            }
+            if read_entry(&path)
+                .await
+                .is_some_and(|entry| matches(&entry.url))
+            {
+                remove_file(&path).await?;
+            }
+        }
+        Ok(())
+    }
+
+    fn memory(&self) -> MutexGuard<'_, LruCache<String, Entry>> {
+        self.memory.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn ttl_of(&self, endpoint: Endpoint) -> Duration {
+        self.ttls
+            .get(&endpoint)
+            .copied()
+            .unwrap_or_else(|| endpoint.default_ttl())
+    }
+
+    fn disk_path(&self, key: &str) -> Option<PathBuf> {
+        let dir = self.disk.as_ref()?;
+        Some(dir.join(format!("{:x}.json", Sha256::digest(key))))
+    }
+
+    /// The cached response of `url` with the request body `request` and authorisation `auth`,
+    /// from memory, or from disk if it is not in memory
+    async fn get(&self, url: &Url, request: Option<&str>, auth: Option<&str>) -> Option<Entry> {
+        let key = key(url, request, auth);
+        if let Some(entry) = self.memory().get(&key) {
+            return Some(entry.clone());
+        }
+        let entry = read_entry(&self.disk_path(&key)?).await?;
+        // Guard against hash collisions
+        if entry.url != *url || entry.request.as_deref() != request || entry.auth.as_deref() != auth
+        {
+            return None;
+        }
+        self.memory().put(key, entry.clone());
+        Some(entry)
+    }
+
+    /// Cache `entry`. Failing to write it to disk is not an error, as it is still cached in memory.
+    async fn put(&self, entry: Entry) {
+        let key = key(&entry.url, entry.request.as_deref(), entry.auth.as_deref());
+        if let Some(path) = self.disk_path(&key) {
+            if let Ok(json) = serde_json::to_vec(&entry) {
+                if let Some(dir) = path.parent() {
+                    let _ = fs::create_dir_all(dir).await;
+                }
+                let _ = fs::write(path, json).await;
+            }
+        }
+        self.memory().put(key, entry);
+    }
+
+    /// Send `request` of `endpoint`, using and updating the cache according to its policy and offline mode,
+    /// and return the response body
+    pub(crate) async fn fetch(
+        &self,
+        request: RequestBuilder,
+        endpoint: Endpoint,
+    ) -> Result<String> {
+        let RequestBuilder {
+            inner: mut request,
+            url,
+            json,
+            auth,
+            cache_policy: policy,
+            offline_mode,
+            untracked_stale,
+            transport,
+            ..
+        } = request;
+        let cached = match policy {
+            // Refreshed responses are still used when the server cannot be contacted
+            CachePolicy::Use | CachePolicy::Refresh => {
+                self.get(&url, json.as_deref(), auth.as_deref()).await
+            }
+            CachePolicy::Bypass => None,
+        };
+        let ttl = self.ttl_of(endpoint);
+
+        if offline_mode == OfflineMode::Always {
+            return match cached {
+                Some(cached) if cached.age() < ttl => Ok(cached.body),
+                Some(cached) => use_stale(cached, untracked_stale),
+                None => Err(Error::Offline(url)),
+            };
+        }
+
+        if let Some(cached) = cached.as_ref().filter(|_| policy == CachePolicy::Use) {
+            if cached.age() < ttl {
+                return Ok(cached.body.clone());
+            }
+            if let Some(etag) = &cached.etag {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &cached.last_modified {
+                request = request.header(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+
+        let response = match transport.send(request).await {
+            Err(Error::ReqwestError(err))
+                if offline_mode == OfflineMode::Fallback
+                    && (err.is_connect() || err.is_timeout()) =>
+            {
+                return match cached {
+                    Some(cached) => use_stale(cached, untracked_stale),
+                    None => Err(Error::Offline(url)),
+                };
+            }
+            response => response?,
+        };
+        let entry = match cached {
+            Some(mut cached) if response.status() == StatusCode::NOT_MODIFIED => {
+                cached.stored = chrono::Utc::now();
+                cached
+            }
+            _ => {
+                let headers = response.headers().clone();
+                Entry {
+                    url,
+                    request: json,
+                    auth,
+                    body: response.text().await?,
+                    etag: header(&headers, ETAG),
+                    last_modified: header(&headers, LAST_MODIFIED),
+                    stored: chrono::Utc::now(),
+                }
+            }
+        };
+        let body = entry.body.clone();
+        if policy != CachePolicy::Bypass {
+            self.put(entry).await;
+        }
+        Ok(body)
+    }
+}
+
+/// The key of the response of `url` with the request body `request` and authorisation `auth`
+fn key(url: &Url, request: Option<&str>, auth: Option<&str>) -> String {
+    let mut key = url.to_string();
+    for part in [request, auth].into_iter().flatten() {
+        key.push(' ');
+        key.push_str(part);
+    }
+    key
+}
+
+fn header(headers: &HeaderMap, name: reqwest::header::HeaderName) -> Option<String> {
+    headers.get(name)?.to_str().ok().map(str::to_owned)
+}
+
+/// Read the entry at `path`, treating unreadable entries as missing
+async fn read_entry(path: &Path) -> Option<Entry> {
+    serde_json::from_slice(&fs::read(path).await.ok()?).ok()
+}
+
+async fn remove_file(path: &Path) -> Result<()> {
+    match fs::remove_file(path).await {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+        _ => Ok(()),
+    }
+}
+
+impl<T> Ferinth<T> {
+    /// Cache the responses of API calls made with this container in `cache`
+    pub fn with_cache(mut self, cache: Cache) -> Self {
+        self.client.cache = Some(Arc::new(cache));
+        self
+    }
+
+    /// The cache of this container, if it has one
+    pub fn cache(&self) -> Option<&Cache> {
+        self.client.cache.as_deref()
+    }
+
+    /**
+    A copy of this container that uses its cache according to `policy`,
+    to bypass or refresh the cache for particular API calls
+
+    ## Example
+    ```no_run
+    # use ferinth::cache::{Cache, CachePolicy};
+    # tokio_test::block_on(async {
+    let modrinth = ferinth::Ferinth::default().with_cache(Cache::new(256));
+    // Get the latest versions even if older ones are cached
+    let versions = modrinth
+        .cache_policy(CachePolicy::Refresh)
+        .version_list("sodium")
+        .await?;
+    # Ok::<_, ferinth::Error>(()) }).unwrap()
+    ```
+    */
+    pub fn cache_policy(&self, policy: CachePolicy) -> Self {
+        let mut client = self.client.clone();
+        client.cache_policy = policy;
+        Self {
+            client,
+            auth: std::marker::PhantomData,
+        }
+    }
+
+    /**
+    A copy of this container that contacts the server according to `mode`,
+    using expired cached responses when it cannot or should not be contacted
+
+    ## Example
+    ```no_run
+    # use ferinth::cache::{Cache, OfflineMode};
+    # tokio_test::block_on(async {
+    let modrinth = ferinth::Ferinth::default()
+        .with_cache(Cache::new(256).disk("cache/modrinth"))
+        .offline_mode(OfflineMode::Always);
+    match modrinth.project_get("sodium").await {
+        Err(ferinth::Error::Offline(_)) => println!("Sodium has not been cached"),
+        result => println!("{}", result?.title),
+    }
+    # Ok::<_, ferinth::Error>(()) }).unwrap()
+    ```
+    */
+    pub fn offline_mode(&self, mode: OfflineMode) -> Self {
+        let mut client = self.client.clone();
+        client.offline_mode = mode;
+        Self {
+            client,
+            auth: std::marker::PhantomData,
+        }
+    }
+
+    /**
+    A copy of this container that uses expired cached responses according to its [`OfflineMode`]
+    even outside of [`track_freshness`], where it cannot be reported that they are stale
+
+    ## Example
+    ```no_run
+    # use ferinth::cache::{Cache, OfflineMode};
+    # tokio_test::block_on(async {
+    let modrinth = ferinth::Ferinth::default()
+        .with_cache(Cache::new(256).disk("cache/modrinth"))
+        .offline_mode(OfflineMode::Fallback)
+        .allow_untracked_stale();
+    // Show Sodium even if it is outdated, such as in a spawned task
+    let sodium = tokio::spawn(async move { modrinth.project_get("sodium").await }).await.unwrap()?;
+    # Ok::<_, ferinth::Error>(()) }).unwrap()
+    ```
+    */
+    pub fn allow_untracked_stale(&self) -> Self {
+        let mut client = self.client.clone();
+        client.untracked_stale = true;
+        Self {
+            client,
+            auth: std::marker::PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        request::{Client, RequestBuilderCustomSend},
+        test_server::TestServer,
+        API_BASE_URL,
+    };
+    use reqwest::header::HeaderValue;
+    use std::sync::atomic::Ordering;
+
+    const LOADERS: &[u8] = br#"["fabric","forge"]"#;
+
+    fn client(cache: Cache, policy: CachePolicy) -> Client {
+        let mut client = Client::new(reqwest::Client::new());
+        client.cache = Some(Arc::new(cache));
+        client.cache_policy = policy;
+        client
+    }
+
+    async fn get(client: &Client, url: &Url) -> Result<Vec<String>> {
+        client.get(url.clone()).custom_send_json().await
+    }
+
+    #[tokio::test]
+    async fn policies() -> Result<()> {
+        let server = TestServer::serve(&[("v2/tag/loader", LOADERS)]).await;
+        let url = server.url.join("v2/tag/loader").unwrap();
+        let mut client = client(Cache::new(8), CachePolicy::Use);
+
+        assert_eq!(get(&client, &url).await?, ["fabric", "forge"]);
+        get(&client, &url).await?;
+        assert_eq!(server.requests.load(Ordering::SeqCst), 1);
+
+        client.cache_policy = CachePolicy::Refresh;
+        get(&client, &url).await?;
+        client.cache_policy = CachePolicy::Bypass;
+        get(&client, &url).await?;
+        assert_eq!(server.requests.load(Ordering::SeqCst), 3);
+
+        client.cache_policy = CachePolicy::Use;
+        get(&client, &url).await?;
+        assert_eq!(server.requests.load(Ordering::SeqCst), 3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn revalidates_stale_responses() -> Result<()> {
+        let server = TestServer::serve(&[("v2/tag/loader", LOADERS)]).await;
+        let url = server.url.join("v2/tag/loader").unwrap();
+        let client = client(
+            Cache::new(8).ttl(Endpoint::Tag, Duration::ZERO),
+            CachePolicy::Use,
+        );
+
+        get(&client, &url).await?;
+        // The server responds with an empty `304 Not Modified`, so the cached body is used
+        assert_eq!(get(&client, &url).await?, ["fabric", "forge"]);
+        assert_eq!(server.requests.load(Ordering::SeqCst), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn disk_store() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let server = TestServer::serve(&[("v2/tag/loader", LOADERS)]).await;
+        let url = server.url.join("v2/tag/loader").unwrap();
+
+        get(
+            &client(Cache::new(8).disk(dir.path()), CachePolicy::Use),
+            &url,
+        )
+        .await?;
+        let cache = Cache::new(8).disk(dir.path());
+        let client = client(cache, CachePolicy::Use);
+        assert_eq!(get(&client, &url).await?, ["fabric", "forge"]);
+        assert_eq!(server.requests.load(Ordering::SeqCst), 1);
+
+        let cache = client.cache.as_ref().unwrap();
+        cache.invalidate_endpoint(Endpoint::Project).await?;
+        get(&client, &url).await?;
+        assert_eq!(server.requests.load(Ordering::SeqCst), 1);
+
+        // Files that are not cached responses are left alone
+        std::fs::write(dir.path().join("notes.txt"), "")?;
+        std::fs::write(dir.path().join("other.json"), "{}")?;
+        std::fs::create_dir(dir.path().join("subdirectory"))?;
+        cache.invalidate_endpoint(Endpoint::Tag).await?;
+        assert_eq!(std::fs::read_dir(dir.path())?.count(), 3);
+        get(&client, &url).await?;
+        assert_eq!(server.requests.load(Ordering::SeqCst), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn authorisation() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let server = TestServer::serve(&[("v2/tag/loader", LOADERS)]).await;
+        let url = server.url.join("v2/tag/loader").unwrap();
+        let client = |token: Option<&str>| {
+            let mut client = client(Cache::new(8).disk(dir.path()), CachePolicy::Use);
+            client.api_host = url.host_str().map(str::to_owned);
+            match token {
+                Some(token) => client.authenticated(HeaderValue::from_str(token).unwrap()),
+                None => client,
+            }
+        };
+
+        get(&client(Some("a")), &url).await?;
+        get(&client(Some("a")), &url).await?;
+        assert_eq!(server.requests.load(Ordering::SeqCst), 1);
+        // Responses to other tokens, or to no token, are not shared
+        get(&client(Some("b")), &url).await?;
+        get(&client(None), &url).await?;
+        assert_eq!(server.requests.load(Ordering::SeqCst), 3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn mutations_invalidate() -> Result<()> {
+        let server =
+            TestServer::serve(&[("v2/tag/loader", LOADERS), ("v2/project/sodium", b"[]")]).await;
+        let loaders = server.url.join("v2/tag/loader").unwrap();
+        let project = server.url.join("v2/project/sodium").unwrap();
+        let client = client(Cache::new(8), CachePolicy::Use);
+
+        get(&client, &loaders).await?;
+        get(&client, &project).await?;
+        client.patch(project.clone()).custom_send().await?;
+        assert_eq!(server.requests.load(Ordering::SeqCst), 3);
+
+        get(&client, &loaders).await?;
+        get(&client, &project).await?;
+        assert_eq!(server.requests.load(Ordering::SeqCst), 4);
+        Ok(())
+    }
+
+    /// A URL that refuses connections
+    async fn unreachable_url() -> Url {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/v2/tag/loader", listener.local_addr().unwrap());
+        Url::parse(&url).unwrap()
+    }
+
+    fn entry(url: &Url, request: Option<&str>, age: chrono::Duration) -> Entry {
+        Entry {
+            url: url.clone(),
+            request: request.map(str::to_owned),
+            auth: None,
+            body: String::from_utf8(LOADERS.to_vec()).unwrap(),
+            etag: None,
+            last_modified: None,
+            stored: chrono::Utc::now() - age,
+        }
+    }
+
+    #[tokio::test]
+    async fn offline_always() -> Result<()> {
+        let server = TestServer::serve(&[("v2/tag/loader", LOADERS)]).await;
+        let url = server.url.join("v2/tag/loader").unwrap();
+        let mut client = client(Cache::new(8), CachePolicy::Use);
+        client.offline_mode = OfflineMode::Always;
+
+        assert!(matches!(get(&client, &url).await, Err(Error::Offline(_))));
+        assert!(matches!(
+            client.get(url.clone()).custom_send().await,
+            Err(Error::Offline(_))
+        ));
+
+        let cache = client.cache.as_ref().unwrap();
+        cache.put(entry(&url, None, chrono::Duration::zero())).await;
+        let (loaders, freshness) = track_freshness(get(&client, &url)).await;
+        assert_eq!(loaders?, ["fabric", "forge"]);
+        assert_eq!(freshness, Freshness::Fresh);
+
+        let stale = entry(&url, None, chrono::Duration::days(2));
+        let stored = stale.stored;
+        cache.put(stale).await;
+        let (loaders, freshness) = track_freshness(get(&client, &url)).await;
+        assert_eq!(loaders?, ["fabric", "forge"]);
+        assert_eq!(freshness, Freshness::Stale { since: stored });
+        // Stale responses are errors unless their staleness is tracked, or untracked staleness is allowed
+        assert!(matches!(
+            get(&client, &url).await,
+            Err(Error::Stale { since, .. }) if since == stored
+        ));
+        client.untracked_stale = true;
+        assert_eq!(get(&client, &url).await?, ["fabric", "forge"]);
+
+        assert_eq!(server.requests.load(Ordering::SeqCst), 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn offline_fallback() -> Result<()> {
+        let url = unreachable_url().await;
+        let mut client = client(Cache::new(8), CachePolicy::Refresh);
+
+        assert!(matches!(
+            get(&client, &url).await,
+            Err(Error::ReqwestError(_))
+        ));
+        client.offline_mode = OfflineMode::Fallback;
+        assert!(matches!(get(&client, &url).await, Err(Error::Offline(_))));
+
+        let cache = client.cache.as_ref().unwrap();
+        cache.put(entry(&url, None, chrono::Duration::zero())).await;
+        let (loaders, freshness) = track_freshness(get(&client, &url)).await;
+        assert_eq!(loaders?, ["fabric", "forge"]);
+        assert!(freshness.is_stale());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn hash_lookups() -> Result<()> {
+        let url = unreachable_url().await.join("/v2/version_files").unwrap();
+        let mut client = client(Cache::new(8), CachePolicy::Use);
+        client.offline_mode = OfflineMode::Always;
+        let cache = client.cache.as_ref().unwrap();
+        cache
+            .put(entry(
+                &url,
+                Some(r#"{"hashes":["a"]}"#),
+                chrono::Duration::zero(),
+            ))
+            .await;
+
+        let lookup = |hash: &str| {
+            client
+                .post(url.clone())
+                .json(&serde_json::json!({ "hashes": [hash] }))
+                .custom_send_json::<Vec<String>>()
+        };
+        assert_eq!(lookup("a").await?, ["fabric", "forge"]);
+        assert!(matches!(lookup("b").await, Err(Error::Offline(_))));
+
+        // Update checks of a single file are cached too
+        let update = url.join("version_file/a/update?algorithm=sha512").unwrap();
+        let body = r#"{"loaders":["fabric"]}"#;
+        cache
+            .put(entry(&update, Some(body), chrono::Duration::zero()))
+            .await;
+        let latest: Vec<String> = client
+            .post(update)
+            .json(&serde_json::json!({ "loaders": ["fabric"] }))
+            .custom_send_json()
+            .await?;
+        assert_eq!(latest, ["fabric", "forge"]);
+        Ok(())
+    }
+
+    #[test]
+    fn endpoints() {
+        let url = |path| API_BASE_URL.join(path).unwrap();
+        for path in [
+            "version_files",
+            "version_files/update",
+            "version_file/a/update",
+        ] {
+            assert_eq!(
+                Endpoint::from_request(&Method::POST, &url(path)),
+                Some(Endpoint::Version)
+            );
+        }
+        assert_eq!(Endpoint::from_request(&Method::POST, &url("version")), None);
+        assert_eq!(
+            Endpoint::from_request(&Method::DELETE, &url("version_file/a")),
+            None
+        );
+        assert_eq!(
+            Endpoint::from_request(&Method::GET, &url("version_file/a")),
+            Some(Endpoint::Version)
+        );
+        assert_eq!(
+            Endpoint::from_request(&Method::GET, &url("projects_random")),
+            None
+        );
+    }
+}
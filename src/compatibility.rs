@@ -0,0 +1,306 @@
+//! Find incompatibilities and missing dependencies in a set of installed versions
+
+use crate::{
+    hashing::hash_path,
+    structures::{
+        id::{IntoId, ProjectId, VersionId},
+        version::{DependencyType, Version},
+    },
+    update_checker::HashLookup,
+    Ferinth, Result,
+};
+use std::path::PathBuf;
+
+/// A problem with a set of installed versions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    /// The version of `version_id` is incompatible with the installed version of `incompatible`
+    Incompatible {
+        version_id: VersionId,
+        incompatible: VersionId,
+    },
+    /// The version of `version_id` requires the project of `project_id`,
+    /// or specifically the version of `dependency_version_id`, which is not installed
+    MissingDependency {
+        version_id: VersionId,
+        project_id: Option<ProjectId>,
+        dependency_version_id: Option<VersionId>,
+    },
+    /// The version of `version_id` does not support any of the instance's loaders
+    UnsupportedLoader {
+        version_id: VersionId,
+        loaders: Vec<String>,
+    },
+    /// The version of `version_id` does not support any of the instance's game versions
+    UnsupportedGameVersion {
+        version_id: VersionId,
+        game_versions: Vec<String>,
+    },
+}
+
+/// The issues found in a set of installed versions
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    pub issues: Vec<Issue>,
+    /// The files that are not known to Modrinth, which could not be checked
+    pub unknown_files: Vec<PathBuf>,
+}
+
+impl Report {
+    /// Whether no issues were found
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/**
+Checks that a set of installed versions work together in an instance
+
+## Example
+```no_run
+# use ferinth::compatibility::CompatibilityChecker;
+# tokio_test::block_on(async {
+# let modrinth = ferinth::Ferinth::default();
+let checker = CompatibilityChecker::new(vec!["fabric".into()], vec!["1.20.1".into()]);
+let report = checker.check_ids(&modrinth, &["xuWxRZPd", "sxWTUZpD"]).await?;
+for issue in &report.issues {
+    eprintln!("{issue:?}");
+}
+# Ok::<_, ferinth::Error>(()) }).unwrap()
+```
+*/
+#[derive(Debug, Clone)]
+pub struct CompatibilityChecker {
+    loaders: Vec<String>,
+    game_versions: Vec<String>,
+}
+
+impl CompatibilityChecker {
+    /// Check versions for an instance with the `loaders` and `game_versions`,
+    /// where empty lists are not checked
+    pub fn new(loaders: Vec<String>, game_versions: Vec<String>) -> Self {
+        Self {
+            loaders,
+            game_versions,
+        }
+    }
+
+    /// Check the installed `versions`
+    pub fn check(&self, versions: &[Version]) -> Report {
+        let mut issues = vec![];
+        let installed_version = |version_id: &VersionId| {
+            versions
+                .iter()
+                .find(|version| version.id == *version_id)
+                .map(|version| version.id.clone())
+        };
+        let installed_project = |project_id: &ProjectId| {
+            versions
+                .iter()
+                .find(|version| version.project_id == *project_id)
+                .map(|version| version.id.clone())
+        };
+
+        for version in versions {
+            if !self.loaders.is_empty() && !version.loaders.iter().any(|l| self.loaders.contains(l))
+            {
+                issues.push(Issue::UnsupportedLoader {
+                    version_id: version.id.clone(),
+                    loaders: version.loaders.clone(),
+                });
+            }
+            if !self.game_versions.is_empty()
+                && !version
+                    .game_versions
+                    .iter()
+                    .any(|v| self.game_versions.contains(v))
+            {
+                issues.push(Issue::UnsupportedGameVersion {
+                    version_id: version.id.clone(),
+                    game_versions: version.game_versions.clone(),
+                });
+            }
+
+            for dependency in &version.dependencies {
+                match dependency.dependency_type {
+                    DependencyType::Incompatible => {
+                        let incompatible = match (&dependency.version_id, &dependency.project_id) {
+                            (Some(version_id), _) => installed_version(version_id),
+                            (None, Some(project_id)) => installed_project(project_id),
+                            (None, None) => None,
+                        };
+                        if let Some(incompatible) = incompatible {
+                            issues.push(Issue::Incompatible {
+                                version_id: version.id.clone(),
+                                incompatible,
+                            });
+                        }
+                    }
+                    DependencyType::Required => {
+                        // Any version of the project satisfies the dependency
+                        let installed = dependency
+                            .project_id
+                            .as_ref()
+                            .and_then(installed_project)
+                            .or_else(|| dependency.version_id.as_ref().and_then(installed_version));
+                        // Dependencies on files outside Modrinth cannot be checked
+                        let checkable =
+                            dependency.project_id.is_some() || dependency.version_id.is_some();
+                        if checkable && installed.is_none() {
+                            issues.push(Issue::MissingDependency {
+                                version_id: version.id.clone(),
+                                project_id: dependency.project_id.clone(),
+                                dependency_version_id: dependency.version_id.clone(),
+                            });
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Report {
+            issues,
+            unknown_files: vec![],
+        }
+    }
+
+    /// Check the installed versions of `version_ids`
+    pub async fn check_ids<T>(
+        &self,
+        modrinth: &Ferinth<T>,
+        version_ids: &[impl IntoId<VersionId> + Clone],
+    ) -> Result<Report> {
+        Ok(self.check(&modrinth.version_get_multiple(version_ids).await?))
+    }
+
+    /// Check the versions of the files at `paths`, looking them up by their hashes
+    pub async fn check_files<T>(
+        &self,
+        modrinth: &Ferinth<T>,
+        paths: Vec<PathBuf>,
+    ) -> Result<Report> {
+        self.check_files_from(modrinth, paths).await
+    }
+
+    async fn check_files_from(
+        &self,
+        source: &impl HashLookup,
+        paths: Vec<PathBuf>,
+    ) -> Result<Report> {
+        let mut hashes = Vec::with_capacity(paths.len());
+        for path in &paths {
+            hashes.push(hash_path(path).await?.sha512);
+        }
+        if hashes.is_empty() {
+            return Ok(Report::default());
+        }
+
+        let versions = source.versions(hashes.clone()).await?;
+        let mut installed: Vec<Version> = vec![];
+        let mut unknown_files = vec![];
+        for (path, hash) in paths.into_iter().zip(hashes) {
+            match versions.get(&hash) {
+                // Identical files are the same version, which is only checked once
+                Some(version) if installed.iter().any(|v| v.id == version.id) => {}
+                Some(version) => installed.push(version.clone()),
+                None => unknown_files.push(path),
+            }
+        }
+        Ok(Report {
+            unknown_files,
+            ..self.check(&installed)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_versions::{version, TestLookup};
+    use serde_json::json;
+
+    #[test]
+    fn compatible() {
+        let report =
+            CompatibilityChecker::new(vec!["fabric".into()], vec!["1.20.1".into()]).check(&[
+                version("aaaaaaa1", "AAAAAAAA")
+                    .dependencies(json!([
+                        { "project_id": "BBBBBBBB", "dependency_type": "required" },
+                        { "project_id": "CCCCCCCC", "dependency_type": "optional" },
+                        { "file_name": "library.jar", "dependency_type": "required" },
+                    ]))
+                    .build(),
+                version("bbbbbbb1", "BBBBBBBB").build(),
+            ]);
+        assert!(report.is_ok());
+    }
+
+    #[test]
+    fn issues() {
+        let report =
+            CompatibilityChecker::new(vec!["fabric".into()], vec!["1.20.1".into()]).check(&[
+                version("aaaaaaa1", "AAAAAAAA")
+                    .dependencies(json!([
+                        { "project_id": "BBBBBBBB", "dependency_type": "incompatible" },
+                        { "project_id": "CCCCCCCC", "version_id": "ccccccc1", "dependency_type": "required" },
+                    ]))
+                    .build(),
+                version("bbbbbbb1", "BBBBBBBB")
+                    .loader("forge")
+                    .game_version("1.19.4")
+                    .build(),
+            ]);
+        assert_eq!(
+            report.issues,
+            [
+                Issue::Incompatible {
+                    version_id: "aaaaaaa1".parse().unwrap(),
+                    incompatible: "bbbbbbb1".parse().unwrap(),
+                },
+                Issue::MissingDependency {
+                    version_id: "aaaaaaa1".parse().unwrap(),
+                    project_id: Some("CCCCCCCC".parse().unwrap()),
+                    dependency_version_id: Some("ccccccc1".parse().unwrap()),
+                },
+                Issue::UnsupportedLoader {
+                    version_id: "bbbbbbb1".parse().unwrap(),
+                    loaders: vec!["forge".parse().unwrap()],
+                },
+                Issue::UnsupportedGameVersion {
+                    version_id: "bbbbbbb1".parse().unwrap(),
+                    game_versions: vec!["1.19.4".into()],
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn check_files() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let paths = ["a.jar", "a copy.jar", "unknown.jar"]
+            .map(|name| dir.path().join(name))
+            .to_vec();
+        for (path, data) in paths.iter().zip([&b"a1"[..], b"a1", b"?"]) {
+            tokio::fs::write(path, data).await?;
+        }
+        let url = "https://cdn.modrinth.com/a1.jar".parse().unwrap();
+        let lookup = TestLookup(vec![version("aaaaaaa1", "AAAAAAAA")
+            .dependencies(json!([{ "project_id": "BBBBBBBB", "dependency_type": "required" }]))
+            .file("a.jar", b"a1", url)
+            .build()]);
+
+        let report = CompatibilityChecker::new(vec![], vec![])
+            .check_files_from(&lookup, paths.clone())
+            .await?;
+        assert_eq!(report.unknown_files, [paths[2].clone()]);
+        assert_eq!(
+            report.issues,
+            [Issue::MissingDependency {
+                version_id: "aaaaaaa1".parse().unwrap(),
+                project_id: Some("BBBBBBBB".parse().unwrap()),
+                dependency_version_id: None,
+            }]
+        );
+        Ok(())
+    }
+}
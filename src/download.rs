@@ -0,0 +1,658 @@
+//! Download version files and verify them against their hashes
+
+use crate::{
+    hashing::{hash_path, FileHashes, Hasher},
+    request::{Client, RequestBuilderCustomSend},
+    structures::{
+        version::{Hash, HashAlgorithm, VersionFile},
+        Int,
+    },
+    Error, Ferinth, Result,
+};
+use futures_util::{stream, StreamExt};
+use reqwest::{
+    header::{HeaderMap, CONTENT_RANGE, RANGE},
+    StatusCode,
+};
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+use tokio::{
+    fs::{self, OpenOptions},
+    io::{AsyncReadExt, AsyncWriteExt},
+};
+use url::Url;
+
+/// A file to download to `destination`, along with the details to verify it with
+#[derive(Debug, Clone)]
+pub struct Download {
+    pub url: Url,
+    pub destination: PathBuf,
+    /// The size of the file in bytes
+    pub size: Int,
+    pub hashes: Hash,
+}
+
+impl Download {
+    /// Download the version `file` to `destination`
+    pub fn new(file: &VersionFile, destination: impl Into<PathBuf>) -> Self {
+        Self {
+            url: file.url.clone(),
+            destination: destination.into(),
+            size: file.size,
+            hashes: file.hashes.clone(),
+        }
+    }
+}
+
+/// What was done to get a file to its destination
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DownloadOutcome {
+    /// The file was downloaded from the start
+    Downloaded,
+    /// The file was partially downloaded before, and the rest of it was downloaded
+    Resumed,
+    /// A file with the same hashes was already present at the destination
+    AlreadyPresent,
+}
+
+/// An update on the progress of a batch of downloads
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    /// The index of the file in the batch
+    pub index: usize,
+    /// The number of bytes of the file that have been downloaded
+    pub downloaded: Int,
+    /// The size of the file in bytes
+    pub size: Int,
+    /// The number of bytes of all the files in the batch that have been downloaded
+    pub total_downloaded: Int,
+    /// The size of all the files in the batch in bytes
+    pub total_size: Int,
+}
+
+type ProgressCallback = Arc<dyn Fn(Progress) + Send + Sync>;
+
+/**
+Downloads many files concurrently, verifying each of them
+
+Files that are already present with matching hashes are skipped,
+and partial downloads left by earlier interrupted runs are resumed.
+
+## Example
+```no_run
+# use ferinth::download::{Download, DownloadManager};
+# tokio_test::block_on(async {
+# let modrinth = ferinth::Ferinth::default();
+let versions = modrinth.version_get_multiple(&["xuWxRZPd", "sxWTUZpD"]).await?;
+let downloads = versions
+    .iter()
+    .map(|version| &version.files[0])
+    .map(|file| Download::new(file, format!("mods/{}", file.filename)))
+    .collect();
+
+let results = DownloadManager::new(8)
+    .on_progress(|progress| {
+        println!("{}/{} bytes", progress.total_downloaded, progress.total_size)
+    })
+    .download_all(&modrinth, downloads)
+    .await;
+for result in results {
+    if let Err(err) = result {
+        eprintln!("{err}");
+    }
+}
+# Ok::<_, ferinth::Error>(()) }).unwrap()
+```
+*/
+#[derive(Clone)]
+pub struct DownloadManager {
+    concurrency: usize,
+    progress: Option<ProgressCallback>,
+}
+
+impl DownloadManager {
+    /// Create a download manager that downloads at most `concurrency` files at a time
+    pub fn new(concurrency: usize) -> Self {
+        Self {
+            concurrency: concurrency.max(1),
+            progress: None,
+        }
+    }
+
+    /// Call `callback` whenever more of a file has been downloaded,
+    /// and when a download fails, as its data no longer counts towards the batch
+    pub fn on_progress(mut self, callback: impl Fn(Progress) + Send + Sync + 'static) -> Self {
+        self.progress = Some(Arc::new(callback));
+        self
+    }
+
+    /**
+    Download all the `downloads` using the client of `modrinth`
+
+    Returns the result of each download in the same order as `downloads`,
+    a failed download does not stop the others.
+    Downloads to the same destination as an earlier one are not started,
+    and return [`Error::DuplicateDestination`].
+    */
+    pub async fn download_all<T>(
+        &self,
+        modrinth: &Ferinth<T>,
+        downloads: Vec<Download>,
+    ) -> Vec<Result<DownloadOutcome>> {
+        let mut destinations = HashSet::new();
+        let duplicates = downloads
+            .iter()
+            .map(|download| !destinations.insert(download.destination.clone()))
+            .collect::<Vec<_>>();
+        let total_size = downloads
+            .iter()
+            .zip(&duplicates)
+            .filter(|(_, duplicate)| !**duplicate)
+            .map(|(download, _)| download.size)
+            .sum();
+        let total_downloaded = Arc::new(AtomicUsize::new(0));
+
+        stream::iter(downloads.into_iter().zip(duplicates).enumerate())
+            .map(|(index, (download, duplicate))| {
+                let total_downloaded = total_downloaded.clone();
+                let progress = self.progress.clone();
+                async move {
+                    // Downloading to the same temporary file at once would corrupt it
+                    if duplicate {
+                        return Err(Error::DuplicateDestination(download.destination));
+                    }
+                    let report = |downloaded, total_downloaded| {
+                        if let Some(progress) = &progress {
+                            progress(Progress {
+                                index,
+                                downloaded,
+                                size: download.size,
+                                total_downloaded,
+                                total_size,
+                            });
+                        }
+                    };
+                    let mut downloaded = 0;
+                    let result = download_verified(&modrinth.client, &download, |len| {
+                        downloaded += len;
+                        report(
+                            downloaded,
+                            total_downloaded.fetch_add(len, Ordering::Relaxed) + len,
+                        );
+                    })
+                    .await;
+                    // The data of a failed download does not count towards the batch
+                    if result.is_err() && downloaded > 0 {
+                        report(
+                            0,
+                            total_downloaded.fetch_sub(downloaded, Ordering::Relaxed) - downloaded,
+                        );
+                    }
+                    result
+                }
+            })
+            .buffered(self.concurrency)
+            .collect()
+            .await
+    }
+}
+
+impl<T> Ferinth<T> {
+    /**
+    Download `file` to `destination`, verifying its size and hashes
+
+    The file is first downloaded to a temporary file next to `destination`,
+    which is moved to `destination` only once it has been verified.
+    If verification fails, the temporary file is deleted and
+    [`Error::SizeMismatch`] or [`Error::HashMismatch`] is returned.
+    If the download is interrupted, the temporary file is kept so that the download can be resumed later.
+
+    ## Example
+    ```no_run
+    # tokio_test::block_on(async {
+    # let modrinth = ferinth::Ferinth::default();
+    let sodium = modrinth.version_get("xuWxRZPd").await?;
+    let file = &sodium.files[0];
+    modrinth.download_file(file, format!("mods/{}", file.filename)).await?;
+    # Ok::<_, ferinth::Error>(()) }).unwrap()
+    ```
+    */
+    pub async fn download_file(
+        &self,
+        file: &VersionFile,
+        destination: impl AsRef<Path>,
+    ) -> Result<DownloadOutcome> {
+        download_verified(
+            &self.client,
+            &Download::new(file, destination.as_ref()),
+            |_| {},
+        )
+        .await
+    }
+}
+
+/// The path of the temporary file that `destination` is downloaded to
+fn temporary_path(destination: &Path) -> PathBuf {
+    let mut name = destination.file_name().unwrap_or_default().to_owned();
+    name.push(".part");
+    destination.with_file_name(name)
+}
+
+/// Check that the `actual` size and hashes of a file match the `expected` ones
+fn verify(download: &Download, size: Int, actual: FileHashes) -> Result<()> {
+    if size != download.size {
+        return Err(Error::SizeMismatch {
+            expected: download.size,
+            actual: size,
+        });
+    }
+    for (algorithm, actual) in [
+        (HashAlgorithm::SHA512, actual.sha512),
+        (HashAlgorithm::SHA1, actual.sha1),
+    ] {
+        let expected = download.hashes.get(algorithm).unwrap_or_default();
+        if !expected.eq_ignore_ascii_case(&actual) {
+            return Err(Error::HashMismatch {
+                algorithm,
+                expected: expected.to_owned(),
+                actual,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Download `download` and verify it, calling `progress` with the length of every chunk received
+pub(crate) async fn download_verified(
+    client: &Client,
+    download: &Download,
+    mut progress: impl FnMut(Int),
+) -> Result<DownloadOutcome> {
+    let destination = &download.destination;
+    if fs::try_exists(destination).await?
+        && fs::metadata(destination).await?.len() == download.size as u64
+        && verify(download, download.size, hash_path(destination).await?).is_ok()
+    {
+        progress(download.size);
+        return Ok(DownloadOutcome::AlreadyPresent);
+    }
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+
+    let temporary = temporary_path(destination);
+    match download_to(client, download, &temporary, &mut progress).await {
+        Ok((outcome, size, hashes)) => match verify(download, size, hashes) {
+            Ok(()) => {
+                fs::rename(&temporary, destination).await?;
+                Ok(outcome)
+            }
+            Err(err) => {
+                fs::remove_file(&temporary).await?;
+                Err(err)
+            }
+        },
+        // Keep the partial download so that it can be resumed later
+        Err(err @ (Error::ReqwestError(_) | Error::IOError(_) | Error::Offline(_))) => Err(err),
+        Err(err) => {
+            let _ = fs::remove_file(&temporary).await;
+            Err(err)
+        }
+    }
+}
+
+/// Download `download` to `path`, resuming from the data already in `path` if possible.
+/// Returns the size and hashes of the resulting file.
+async fn download_to(
+    client: &Client,
+    download: &Download,
+    path: &Path,
+    progress: &mut impl FnMut(Int),
+) -> Result<(DownloadOutcome, Int, FileHashes)> {
+    let mut hasher = Hasher::new();
+    let mut received = 0;
+
+    let mut request = client.get(download.url.clone());
+    let existing = match fs::metadata(path).await {
+        Ok(metadata) if 0 < metadata.len() && metadata.len() < download.size as u64 => {
+            request = request.header(RANGE, format!("bytes={}-", metadata.len()));
+            metadata.len() as Int
+        }
+        _ => 0,
+    };
+    let mut response = request.custom_send().await?;
+    let resumed = existing > 0 && response.status() == StatusCode::PARTIAL_CONTENT;
+    if resumed && content_range_start(response.headers()) != Some(existing) {
+        // The server sent a different part of the file, so download all of it instead
+        response = client.get(download.url.clone()).custom_send().await?;
+    }
+
+    let (outcome, mut file) = if resumed && response.status() == StatusCode::PARTIAL_CONTENT {
+        // Hash the data that has already been downloaded
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .open(path)
+            .await?;
+        let mut buf = vec![0; 64 * 1024];
+        loop {
+            match file.read(&mut buf).await? {
+                0 => break,
+                read => {
+                    hasher.update(&buf[..read]);
+                    received += read;
+                }
+            }
+        }
+        progress(received);
+        (DownloadOutcome::Resumed, file)
+    } else {
+        (DownloadOutcome::Downloaded, fs::File::create(path).await?)
+    };
+
+    while let Some(chunk) = response.chunk().await? {
+        received += chunk.len();
+        // Stop early instead of downloading more than expected
+        if received > download.size {
+            return Err(Error::SizeMismatch {
+                expected: download.size,
+                actual: received,
+            });
+        }
+        hasher.update(&chunk);
+        file.write_all(&chunk).await?;
+        progress(chunk.len());
+    }
+    file.flush().await?;
+
+    Ok((outcome, received, hasher.finish()))
+}
+
+/// The position of the first byte in the `Content-Range` of a partial response
+fn content_range_start(headers: &HeaderMap) -> Option<Int> {
+    headers
+        .get(CONTENT_RANGE)?
+        .to_str()
+        .ok()?
+        .strip_prefix("bytes ")?
+        .split_once('-')?
+        .0
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hashing::hash_bytes, test_server::TestServer};
+    use std::sync::Mutex;
+
+    const DATA: &[u8] = b"The quick brown fox jumps over the lazy dog";
+
+    fn version_file(url: Url, data: &[u8]) -> VersionFile {
+        let hashes = hash_bytes(data);
+        VersionFile {
+            hashes: Hash {
+                sha512: hashes.sha512,
+                sha1: hashes.sha1,
+                others: Default::default(),
+            },
+            url,
+            filename: "file.jar".to_owned(),
+            primary: true,
+            size: data.len(),
+            file_type: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn verified() -> Result<()> {
+        let server = TestServer::serve(&[("file.jar", DATA)]).await;
+        let dir = tempfile::tempdir()?;
+        let destination = dir.path().join("mods").join("file.jar");
+
+        let file = version_file(server.url.join("file.jar").unwrap(), DATA);
+        let outcome = Ferinth::default()
+            .download_file(&file, &destination)
+            .await?;
+
+        assert_eq!(outcome, DownloadOutcome::Downloaded);
+        assert_eq!(fs::read(&destination).await?, DATA);
+        assert!(!temporary_path(&destination).exists());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn token_not_sent() -> Result<()> {
+        let server = TestServer::serve(&[("file.jar", DATA)]).await;
+        let dir = tempfile::tempdir()?;
+
+        let file = version_file(server.url.join("file.jar").unwrap(), DATA);
+        let modrinth = Ferinth::<crate::Authenticated>::new("test", None, None, "secret")?;
+        modrinth
+            .download_file(&file, dir.path().join("file.jar"))
+            .await?;
+
+        let headers = server.headers.lock().unwrap();
+        assert_eq!(headers.len(), 1);
+        assert!(!headers[0].contains_key("authorization"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn hash_mismatch() -> Result<()> {
+        let server = TestServer::serve(&[("file.jar", DATA)]).await;
+        let dir = tempfile::tempdir()?;
+        let destination = dir.path().join("file.jar");
+
+        let mut file = version_file(server.url.join("file.jar").unwrap(), DATA);
+        file.hashes.sha1 = hash_bytes(b"something else").sha1;
+        let result = Ferinth::default().download_file(&file, &destination).await;
+
+        assert!(matches!(
+            result,
+            Err(Error::HashMismatch {
+                algorithm: HashAlgorithm::SHA1,
+                ..
+            })
+        ));
+        assert!(!destination.exists());
+        assert!(!temporary_path(&destination).exists());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn size_mismatch() -> Result<()> {
+        let server = TestServer::serve(&[("file.jar", DATA)]).await;
+        let dir = tempfile::tempdir()?;
+        let destination = dir.path().join("file.jar");
+
+        let mut file = version_file(server.url.join("file.jar").unwrap(), DATA);
+        file.size -= 1;
+        let result = Ferinth::default().download_file(&file, &destination).await;
+
+        assert!(matches!(result, Err(Error::SizeMismatch { .. })));
+        assert!(!destination.exists());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn resume_partial_download() -> Result<()> {
+        let server = TestServer::serve(&[("file.jar", DATA)]).await;
+        let dir = tempfile::tempdir()?;
+        let destination = dir.path().join("file.jar");
+        fs::write(temporary_path(&destination), &DATA[..10]).await?;
+
+        let file = version_file(server.url.join("file.jar").unwrap(), DATA);
+        let outcome = Ferinth::default()
+            .download_file(&file, &destination)
+            .await?;
+
+        assert_eq!(outcome, DownloadOutcome::Resumed);
+        assert_eq!(fs::read(&destination).await?, DATA);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn mismatched_content_range() -> Result<()> {
+        use tokio::net::TcpListener;
+
+        // A server that ignores the start of `Range` requests, sending the whole file as partial content
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let url = Url::parse(&format!("http://{}/file.jar", listener.local_addr()?)).unwrap();
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = vec![];
+                let mut buf = [0; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let read = stream.read(&mut buf).await.unwrap();
+                    request.extend_from_slice(&buf[..read]);
+                }
+                let ranged = String::from_utf8_lossy(&request)
+                    .to_lowercase()
+                    .contains("range:");
+                let head = if ranged {
+                    format!(
+                        "206 Partial Content\r\nContent-Range: bytes 0-{}/{}",
+                        DATA.len() - 1,
+                        DATA.len()
+                    )
+                } else {
+                    "200 OK".to_owned()
+                };
+                let response = format!(
+                    "HTTP/1.1 {head}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    DATA.len()
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+                stream.write_all(DATA).await.unwrap();
+            }
+        });
+
+        let dir = tempfile::tempdir()?;
+        let destination = dir.path().join("file.jar");
+        fs::write(temporary_path(&destination), &DATA[..10]).await?;
+
+        let outcome = Ferinth::default()
+            .download_file(&version_file(url, DATA), &destination)
+            .await?;
+
+        assert_eq!(outcome, DownloadOutcome::Downloaded);
+        assert_eq!(fs::read(&destination).await?, DATA);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn failed_progress_rolled_back() -> Result<()> {
+        let server = TestServer::serve(&[("file.jar", DATA)]).await;
+        let dir = tempfile::tempdir()?;
+
+        let mut file = version_file(server.url.join("file.jar").unwrap(), DATA);
+        file.hashes.sha1 = hash_bytes(b"something else").sha1;
+        let updates = Arc::new(Mutex::new(vec![]));
+        let results = DownloadManager::new(1)
+            .on_progress({
+                let updates = updates.clone();
+                move |progress| updates.lock().unwrap().push(progress)
+            })
+            .download_all(
+                &Ferinth::default(),
+                vec![Download::new(&file, dir.path().join("file.jar"))],
+            )
+            .await;
+
+        assert!(matches!(results[0], Err(Error::HashMismatch { .. })));
+        let updates = updates.lock().unwrap();
+        assert_eq!(
+            updates.iter().map(|p| p.total_downloaded).max(),
+            Some(DATA.len())
+        );
+        let last = updates.last().unwrap();
+        assert_eq!((last.downloaded, last.total_downloaded), (0, 0));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn batch() -> Result<()> {
+        const OTHER: &[u8] = b"Lorem ipsum dolor sit amet";
+        let server = TestServer::serve(&[("a.jar", DATA), ("b.jar", OTHER)]).await;
+        let dir = tempfile::tempdir()?;
+        // `b.jar` is already present, so it should not be downloaded again
+        fs::write(dir.path().join("b.jar"), OTHER).await?;
+
+        let downloads = vec![
+            Download::new(
+                &version_file(server.url.join("a.jar").unwrap(), DATA),
+                dir.path().join("a.jar"),
+            ),
+            Download::new(
+                &version_file(server.url.join("b.jar").unwrap(), OTHER),
+                dir.path().join("b.jar"),
+            ),
+            Download::new(
+                &version_file(server.url.join("missing.jar").unwrap(), DATA),
+                dir.path().join("c.jar"),
+            ),
+        ];
+        let updates = Arc::new(Mutex::new(vec![]));
+        let results = DownloadManager::new(2)
+            .on_progress({
+                let updates = updates.clone();
+                move |progress| updates.lock().unwrap().push(progress)
+            })
+            .download_all(&Ferinth::default(), downloads)
+            .await;
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(*results[0].as_ref().unwrap(), DownloadOutcome::Downloaded);
+        assert_eq!(
+            *results[1].as_ref().unwrap(),
+            DownloadOutcome::AlreadyPresent
+        );
+        assert!(matches!(results[2], Err(Error::ReqwestError(_))));
+        assert_eq!(fs::read(dir.path().join("a.jar")).await?, DATA);
+
+        let updates = updates.lock().unwrap();
+        let last = updates.iter().max_by_key(|p| p.total_downloaded).unwrap();
+        assert_eq!(last.total_downloaded, DATA.len() + OTHER.len());
+        assert_eq!(last.total_size, 2 * DATA.len() + OTHER.len());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn duplicate_destinations() -> Result<()> {
+        const OTHER: &[u8] = b"Lorem ipsum dolor sit amet";
+        let server = TestServer::serve(&[("a.jar", DATA), ("b.jar", OTHER)]).await;
+        let dir = tempfile::tempdir()?;
+
+        let downloads = vec![
+            Download::new(
+                &version_file(server.url.join("a.jar").unwrap(), DATA),
+                dir.path().join("mod.jar"),
+            ),
+            Download::new(
+                &version_file(server.url.join("b.jar").unwrap(), OTHER),
+                dir.path().join("mod.jar"),
+            ),
+        ];
+        let results = DownloadManager::new(2)
+            .download_all(&Ferinth::default(), downloads)
+            .await;
+
+        assert_eq!(*results[0].as_ref().unwrap(), DownloadOutcome::Downloaded);
+        assert!(matches!(
+            &results[1],
+            Err(Error::DuplicateDestination(path)) if *path == dir.path().join("mod.jar")
+        ));
+        assert_eq!(fs::read(dir.path().join("mod.jar")).await?, DATA);
+        assert_eq!(server.requests.load(Ordering::SeqCst), 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+//! Compute the hashes of local files, to look them up using the version file API calls
+
+use sha1::{Digest, Sha1};
+use sha2::Sha512;
+use std::path::Path;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+/// The size of the chunks that files are read in
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// The hashes of a file, in lowercase hexadecimal
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FileHashes {
+    pub sha1: String,
+    pub sha512: String,
+}
+
+/// Incrementally computes the [`FileHashes`] of data that is fed to it in chunks
+#[derive(Debug, Clone, Default)]
+pub struct Hasher {
+    sha1: Sha1,
+    sha512: Sha512,
+}
+
+impl Hasher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed the next chunk of `data` to the hasher
+    pub fn update(&mut self, data: &[u8]) {
+        self.sha1.update(data);
+        self.sha512.update(data);
+    }
+
+    /// Get the hashes of all the data fed to the hasher
+    pub fn finish(self) -> FileHashes {
+        FileHashes {
+            sha1: format!("{:x}", self.sha1.finalize()),
+            sha512: format!("{:x}", self.sha512.finalize()),
+        }
+    }
+}
+
+/// Compute the hashes of `bytes`
+pub fn hash_bytes(bytes: &[u8]) -> FileHashes {
+    let mut hasher = Hasher::new();
+    hasher.update(bytes);
+    hasher.finish()
+}
+
+/// Compute the hashes of the data in `reader` without reading all of it into memory
+pub async fn hash_reader(mut reader: impl AsyncRead + Unpin) -> std::io::Result<FileHashes> {
+    let mut hasher = Hasher::new();
+    let mut buf = vec![0; CHUNK_SIZE];
+    loop {
+        match reader.read(&mut buf).await? {
+            0 => break,
+            read => hasher.update(&buf[..read]),
+        }
+    }
+    Ok(hasher.finish())
+}
+
+/// Compute the hashes of the file at `path` without reading all of it into memory
+pub async fn hash_path(path: impl AsRef<Path>) -> std::io::Result<FileHashes> {
+    hash_reader(tokio::fs::File::open(path).await?).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ABC_SHA1: &str = "a9993e364706816aba3e25717850c26c9cd0d89d";
+    const ABC_SHA512: &str = "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f";
+
+    #[test]
+    fn bytes() {
+        let hashes = hash_bytes(b"abc");
+        assert_eq!(hashes.sha1, ABC_SHA1);
+        assert_eq!(hashes.sha512, ABC_SHA512);
+    }
+
+    #[tokio::test]
+    async fn reader_matches_bytes() -> std::io::Result<()> {
+        assert_eq!(hash_reader(&b"abc"[..]).await?, hash_bytes(b"abc"));
+
+        // Larger than one chunk, so that the data is hashed over multiple reads
+        let data = (0..CHUNK_SIZE * 3 + 7)
+            .map(|i| (i % 251) as u8)
+            .collect::<Vec<_>>();
+        assert_eq!(hash_reader(&data[..]).await?, hash_bytes(&data));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn path() -> std::io::Result<()> {
+        let file = tempfile::NamedTempFile::new()?;
+        tokio::fs::write(file.path(), b"abc").await?;
+        assert_eq!(hash_path(file.path()).await?.sha1, ABC_SHA1);
+        Ok(())
+    }
+}
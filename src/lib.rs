@@ -15,19 +15,35 @@ The major version of this crate's version directly corresponds to the Modrinth A
 If you want to use the Modrinth API version 2, which is the latest one currently, specify this crate's major version as `2`.
 
 Due to this feature, there will be breaking changes in minor version bumps too!
+
+Calls to version 3 of the API are available separately in the [`v3`] module.
 */
 
 mod api_calls;
+pub mod cache;
+pub mod compatibility;
+pub mod download;
+pub mod hashing;
+pub mod lockfile;
+pub mod mrpack;
+pub mod reference;
 mod request;
+pub mod resolver;
 pub mod structures;
+#[cfg(test)]
+mod test_server;
+#[cfg(test)]
+mod test_versions;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod update_checker;
 mod url_ext;
+pub mod v3;
 
-pub use api_calls::{check_id_slug, check_sha1_hash};
+pub use api_calls::{check_id_slug, check_sha1_hash, check_sha512_hash};
 
-use reqwest::{
-    header::{HeaderMap, HeaderValue, InvalidHeaderValue},
-    Client,
-};
+use request::Client;
+use reqwest::header::{HeaderValue, InvalidHeaderValue};
 use std::{marker::PhantomData, sync::LazyLock};
 use url::Url;
 
@@ -47,8 +63,18 @@ pub static API_BASE_URL: LazyLock<Url> = LazyLock::new(|| {
 pub enum Error {
     #[error("Invalid Modrinth ID or slug")]
     InvalidIDorSlug,
+    #[error("{0:?} is not a valid Modrinth ID of {len} base 62 characters", len = structures::id::ID_LENGTH)]
+    InvalidID(String),
     #[error("Invalid SHA1 hash")]
     InvalidSHA1,
+    #[error("Invalid SHA512 hash")]
+    InvalidSHA512,
+    #[error("{0:?} is not a valid search facet")]
+    InvalidFacet(String),
+    #[error("Only SHA1 and SHA512 hashes are supported")]
+    UnsupportedHashAlgorithm,
+    #[error("{value:?} is not an allowed value of the loader field {field:?}")]
+    InvalidLoaderFieldValue { field: String, value: String },
     #[error("You have been rate limited, please wait for {0} seconds")]
     RateLimitExceeded(usize),
     #[error("The API at {} is deprecated", *API_BASE_URL)]
@@ -56,9 +82,52 @@ pub enum Error {
     ReqwestError(#[from] reqwest::Error),
     JSONError(#[from] serde_json::Error),
     InvalidHeaderValue(#[from] InvalidHeaderValue),
+    #[error("Expected the downloaded file to be {expected} bytes, but it was {actual} bytes")]
+    SizeMismatch { expected: usize, actual: usize },
+    #[error("Expected the downloaded file to have the {algorithm} hash {expected}, but it was {actual}")]
+    HashMismatch {
+        algorithm: structures::version::HashAlgorithm,
+        expected: String,
+        actual: String,
+    },
+    #[error("The version does not have any files")]
+    NoVersionFiles,
+    #[error("More than one file is to be downloaded to {0:?}")]
+    DuplicateDestination(std::path::PathBuf),
+    #[error("Invalid modpack: {0}")]
+    InvalidModpack(String),
+    #[error("The path {0:?} is not a safe relative path")]
+    UnsafePath(String),
+    #[error("The host of {0} is not an allowed download host")]
+    DisallowedDownloadHost(Url),
+    #[error("Offline, and the response of {0} is not cached")]
+    Offline(Url),
+    #[error("The cached response of {url} is stale as of {since}, and its freshness is not being tracked")]
+    Stale { url: Url, since: structures::UtcTime },
+    #[error("No valid fixture was recorded for {0}")]
+    MissingFixture(String),
+    #[error("The project {0} was not found")]
+    ProjectNotFound(structures::ProjectId),
+    #[error("{0:?} is not a link to something on Modrinth")]
+    InvalidReference(String),
+    #[error("The lockfile format version {0} is newer than the supported version {supported}", supported = lockfile::FORMAT_VERSION)]
+    UnsupportedLockfileVersion(structures::Int),
+    #[error("The file {0:?} was not found in its version")]
+    FileNotFound(String),
+    #[error("{0}")]
+    DependencyConflict(#[from] resolver::Conflict),
+    IOError(#[from] std::io::Error),
+    ZipError(#[from] zip::result::ZipError),
 }
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Allows infallible conversions, such as from an ID to itself, where a fallible conversion is accepted
+impl From<std::convert::Infallible> for Error {
+    fn from(value: std::convert::Infallible) -> Self {
+        match value {}
+    }
+}
+
 /**
 An instance of the API to invoke API calls on
 
@@ -92,14 +161,16 @@ pub struct Authenticated;
 impl Default for Ferinth<()> {
     fn default() -> Self {
         Self {
-            client: Client::builder()
-                .user_agent(concat!(
-                    env!("CARGO_CRATE_NAME"),
-                    "/",
-                    env!("CARGO_PKG_VERSION")
-                ))
-                .build()
-                .expect("Failed to initialise TLS backend"),
+            client: Client::new(
+                reqwest::Client::builder()
+                    .user_agent(concat!(
+                        env!("CARGO_CRATE_NAME"),
+                        "/",
+                        env!("CARGO_PKG_VERSION")
+                    ))
+                    .build()
+                    .expect("Failed to initialise TLS backend"),
+            ),
             auth: PhantomData,
         }
     }
@@ -111,7 +182,7 @@ impl<T> Ferinth<T> {
         version: Option<&str>,
         contact: Option<&str>,
     ) -> reqwest::ClientBuilder {
-        Client::builder().user_agent(format!(
+        reqwest::Client::builder().user_agent(format!(
             "{}{}{}",
             name,
             version.map_or("".into(), |version| format!("/{}", version)),
@@ -130,9 +201,11 @@ impl Ferinth<()> {
     pub fn new(name: &str, version: Option<&str>, contact: Option<&str>) -> Self {
         Self {
             auth: PhantomData,
-            client: Self::client_builder(name, version, contact)
-                .build()
-                .expect("Failed to initialise TLS backend"),
+            client: Client::new(
+                Self::client_builder(name, version, contact)
+                    .build()
+                    .expect("Failed to initialise TLS backend"),
+            ),
         }
     }
 }
@@ -145,6 +218,8 @@ impl Ferinth<Authenticated> {
 
     The program `name` is required; `version` and `contact` are optional but recommended.
 
+    The `token` is only sent to the Modrinth API, not to the hosts that files are downloaded from.
+
     Fails if the provided `token` cannot be converted into a `HeaderValue`.
     */
     pub fn new<V>(
@@ -159,13 +234,12 @@ impl Ferinth<Authenticated> {
     {
         Ok(Self {
             auth: PhantomData,
-            client: Self::client_builder(name, version, contact)
-                .default_headers(HeaderMap::from_iter([(
-                    reqwest::header::AUTHORIZATION,
-                    token.try_into()?,
-                )]))
-                .build()
-                .expect("Failed to initialise TLS backend"),
+            client: Client::new(
+                Self::client_builder(name, version, contact)
+                    .build()
+                    .expect("Failed to initialise TLS backend"),
+            )
+            .authenticated(token.try_into()?),
         })
     }
 }
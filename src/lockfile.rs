@@ -0,0 +1,393 @@
+//! Pin a set of versions so that the same files can be installed again
+
+use crate::{
+    hashing::hash_path,
+    mrpack::{index::FileEnv, safe_path},
+    structures::{
+        id::{ProjectId, VersionId},
+        project::Project,
+        version::{LatestVersionBody, Version},
+        Int,
+    },
+    update_checker::HashLookup,
+    Error, Ferinth, Result,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+};
+use tokio::fs;
+use url::Url;
+
+/// The version of the lockfile format written by this crate
+pub const FORMAT_VERSION: Int = 1;
+
+/// The primary file of a pinned version
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct LockEntry {
+    pub project_id: ProjectId,
+    pub version_id: VersionId,
+    pub file_name: String,
+    pub sha512: String,
+    /// The size of the file in bytes
+    pub size: Int,
+    pub url: Url,
+    /// The sides that the file is needed on
+    pub env: FileEnv,
+}
+
+impl LockEntry {
+    /// Pin the primary file of `version` of `project`
+    pub fn new(version: &Version, project: &Project) -> Result<Self> {
+        let file = version.primary_file().ok_or(Error::NoVersionFiles)?;
+        Ok(Self {
+            project_id: version.project_id.clone(),
+            version_id: version.id.clone(),
+            file_name: file.filename.clone(),
+            sha512: file.hashes.sha512.clone(),
+            size: file.size,
+            url: file.url.clone(),
+            env: FileEnv::from_project(project),
+        })
+    }
+}
+
+/**
+A serialisable set of pinned versions, along with the filters used to refresh them
+
+## Example
+```no_run
+# use ferinth::{lockfile::Lockfile, resolver::Resolver};
+# tokio_test::block_on(async {
+# let modrinth = ferinth::Ferinth::default();
+let (loaders, game_versions) = (vec!["fabric".to_owned()], vec!["1.20.1".to_owned()]);
+let resolution = Resolver::new(loaders.clone(), game_versions.clone())
+    .resolve(&modrinth, &["iris"])
+    .await?;
+let versions = resolution
+    .versions
+    .into_iter()
+    .map(|resolved| resolved.version)
+    .collect::<Vec<_>>();
+let lockfile = Lockfile::generate(&modrinth, loaders, game_versions, &versions).await?;
+lockfile.save("mods.lock.json").await?;
+
+let verification = Lockfile::load("mods.lock.json").await?.verify_dir("mods").await?;
+assert!(verification.is_ok());
+# Ok::<_, ferinth::Error>(()) }).unwrap()
+```
+*/
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Lockfile {
+    /// The version of the lockfile format
+    pub format_version: Int,
+    /// The loaders that updates must support one of
+    pub loaders: Vec<String>,
+    /// The game versions that updates must support one of
+    pub game_versions: Vec<String>,
+    /// The pinned files, sorted by project ID
+    pub entries: Vec<LockEntry>,
+}
+
+/// The result of checking a directory against a lockfile
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Verification {
+    /// The entries whose files are not in the directory
+    pub missing: Vec<LockEntry>,
+    /// The entries whose files in the directory have a different size or hash
+    pub modified: Vec<LockEntry>,
+    /// The files in the directory that are not in the lockfile
+    pub untracked: Vec<PathBuf>,
+}
+
+impl Verification {
+    /// Whether the directory matches the lockfile exactly
+    pub fn is_ok(&self) -> bool {
+        self.missing.is_empty() && self.modified.is_empty() && self.untracked.is_empty()
+    }
+}
+
+impl Lockfile {
+    /// Create a lockfile of `entries`, which are updated to versions supporting the `loaders` and `game_versions`
+    pub fn new(
+        loaders: Vec<String>,
+        game_versions: Vec<String>,
+        mut entries: Vec<LockEntry>,
+    ) -> Self {
+        entries.sort_by(|a, b| a.project_id.cmp(&b.project_id));
+        Self {
+            format_version: FORMAT_VERSION,
+            loaders,
+            game_versions,
+            entries,
+        }
+    }
+
+    /// Pin the primary files of `versions`, getting the side support of their projects from `modrinth`
+    pub async fn generate<T>(
+        modrinth: &Ferinth<T>,
+        loaders: Vec<String>,
+        game_versions: Vec<String>,
+        versions: &[Version],
+    ) -> Result<Self> {
+        let project_ids = versions
+            .iter()
+            .map(|version| &version.project_id)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        let projects = if project_ids.is_empty() {
+            HashMap::new()
+        } else {
+            modrinth
+                .project_get_multiple(&project_ids)
+                .await?
+                .into_iter()
+                .map(|project| (project.id.clone(), project))
+                .collect()
+        };
+        let entries = versions
+            .iter()
+            .map(|version| match projects.get(&version.project_id) {
+                Some(project) => LockEntry::new(version, project),
+                None => Err(Error::ProjectNotFound(version.project_id.clone())),
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self::new(loaders, game_versions, entries))
+    }
+
+    /// Read the lockfile at `path`, which must not be in a newer format than [`FORMAT_VERSION`]
+    pub async fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let lockfile: Self = serde_json::from_slice(&fs::read(path).await?)?;
+        if lockfile.format_version > FORMAT_VERSION {
+            return Err(Error::UnsupportedLockfileVersion(lockfile.format_version));
+        }
+        Ok(lockfile)
+    }
+
+    /// Write the lockfile to `path`
+    pub async fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let mut json = serde_json::to_vec_pretty(self)?;
+        json.push(b'\n');
+        fs::write(path, json).await?;
+        Ok(())
+    }
+
+    /// Check that the files directly in `dir` are exactly the files in the lockfile
+    pub async fn verify_dir(&self, dir: impl AsRef<Path>) -> Result<Verification> {
+        let dir = dir.as_ref();
+        let mut verification = Verification::default();
+        for entry in &self.entries {
+            let path = dir.join(safe_path(&entry.file_name)?);
+            if !fs::try_exists(&path).await? {
+                verification.missing.push(entry.clone());
+            } else if fs::metadata(&path).await?.len() != entry.size as u64
+                || !hash_path(&path)
+                    .await?
+                    .sha512
+                    .eq_ignore_ascii_case(&entry.sha512)
+            {
+                verification.modified.push(entry.clone());
+            }
+        }
+
+        let mut files = fs::read_dir(dir).await?;
+        while let Some(file) = files.next_entry().await? {
+            if file.file_type().await?.is_file()
+                && !self
+                    .entries
+                    .iter()
+                    .any(|entry| file.file_name() == entry.file_name.as_str())
+            {
+                verification.untracked.push(file.path());
+            }
+        }
+        verification.untracked.sort();
+        Ok(verification)
+    }
+
+    /**
+    Update the entries to the latest versions of their projects that support the lockfile's filters
+
+    Returns the entries that were updated, as pairs of the old and new entries.
+    */
+    pub async fn refresh<T>(
+        &mut self,
+        modrinth: &Ferinth<T>,
+    ) -> Result<Vec<(LockEntry, LockEntry)>> {
+        self.refresh_from(modrinth).await
+    }
+
+    async fn refresh_from(
+        &mut self,
+        source: &impl HashLookup,
+    ) -> Result<Vec<(LockEntry, LockEntry)>> {
+        if self.entries.is_empty() {
+            return Ok(vec![]);
+        }
+        let latest = source
+            .latest_versions(
+                self.entries
+                    .iter()
+                    .map(|entry| entry.sha512.clone())
+                    .collect(),
+                LatestVersionBody {
+                    loaders: self.loaders.clone(),
+                    game_versions: self.game_versions.clone(),
+                },
+            )
+            .await?;
+
+        let mut updated = vec![];
+        for entry in &mut self.entries {
+            // Entries can share a hash, so the versions are not removed
+            let Some(version) = latest.get(&entry.sha512) else {
+                continue;
+            };
+            if version.id == entry.version_id {
+                continue;
+            }
+            let file = version.primary_file().ok_or(Error::NoVersionFiles)?;
+            let new = LockEntry {
+                project_id: version.project_id.clone(),
+                version_id: version.id.clone(),
+                file_name: file.filename.clone(),
+                sha512: file.hashes.sha512.clone(),
+                size: file.size,
+                url: file.url.clone(),
+                // The project is the same, so its side support is too
+                env: entry.env,
+            };
+            updated.push((std::mem::replace(entry, new.clone()), new));
+        }
+        Ok(updated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        hashing::hash_bytes,
+        mrpack::index::EnvSupport,
+        test_versions::{version, TestLookup},
+    };
+
+    fn entry(project_id: &str, file_name: &str, data: &[u8]) -> LockEntry {
+        LockEntry {
+            project_id: project_id.parse().unwrap(),
+            version_id: format!("{}1", &project_id[1..]).parse().unwrap(),
+            file_name: file_name.to_owned(),
+            sha512: hash_bytes(data).sha512,
+            size: data.len(),
+            url: format!("https://cdn.modrinth.com/data/{project_id}/versions/1/{file_name}")
+                .parse()
+                .unwrap(),
+            env: FileEnv {
+                client: EnvSupport::Required,
+                server: EnvSupport::Optional,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn save_and_load() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("mods.lock.json");
+        let lockfile = Lockfile::new(
+            vec!["fabric".into()],
+            vec!["1.20.1".into()],
+            vec![
+                entry("BBBBBBBB", "b.jar", b"b"),
+                entry("AAAAAAAA", "a.jar", b"a"),
+            ],
+        );
+        assert_eq!(lockfile.entries[0].project_id, "AAAAAAAA");
+
+        lockfile.save(&path).await?;
+        assert_eq!(Lockfile::load(&path).await?, lockfile);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn load_newer_format() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("mods.lock.json");
+        let mut lockfile = Lockfile::new(vec![], vec![], vec![]);
+        lockfile.format_version = FORMAT_VERSION + 1;
+        lockfile.save(&path).await?;
+
+        assert!(matches!(
+            Lockfile::load(&path).await,
+            Err(Error::UnsupportedLockfileVersion(version)) if version == FORMAT_VERSION + 1
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn verify_dir() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(dir.path().join("a.jar"), b"a").await?;
+        fs::write(dir.path().join("b.jar"), b"modified").await?;
+        fs::write(dir.path().join("d.jar"), b"d").await?;
+        let (a, b, c) = (
+            entry("AAAAAAAA", "a.jar", b"a"),
+            entry("BBBBBBBB", "b.jar", b"b"),
+            entry("CCCCCCCC", "c.jar", b"c"),
+        );
+
+        let verification = Lockfile::new(vec![], vec![], vec![a.clone(), b.clone(), c.clone()])
+            .verify_dir(dir.path())
+            .await?;
+        assert_eq!(
+            verification,
+            Verification {
+                missing: vec![c],
+                modified: vec![b],
+                untracked: vec![dir.path().join("d.jar")],
+            }
+        );
+
+        let verification = Lockfile::new(vec![], vec![], vec![a])
+            .verify_dir(dir.path())
+            .await?;
+        assert!(!verification.is_ok());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn refresh() -> Result<()> {
+        let url = |name: &str| format!("https://cdn.modrinth.com/{name}").parse().unwrap();
+        let lookup = TestLookup(vec![
+            version("AAAAAAA1", "AAAAAAAA")
+                .file("a.jar", b"a", url("a1"))
+                .build(),
+            version("AAAAAAA2", "AAAAAAAA")
+                .day(2)
+                .file("a2.jar", b"a2", url("a2"))
+                .build(),
+            version("BBBBBBB1", "BBBBBBBB")
+                .file("b.jar", b"b", url("b1"))
+                .build(),
+        ]);
+        // Two entries with the same file are both refreshed
+        let (a, b) = (
+            entry("AAAAAAAA", "a.jar", b"a"),
+            entry("BBBBBBBB", "b.jar", b"b"),
+        );
+        let mut lockfile = Lockfile::new(vec![], vec![], vec![a.clone(), a.clone(), b.clone()]);
+
+        let updated = lockfile.refresh_from(&lookup).await?;
+        assert_eq!(updated.len(), 2);
+        for (old, new) in &updated {
+            assert_eq!(*old, a);
+            assert_eq!(new.version_id, "AAAAAAA2");
+            assert_eq!(new.sha512, hash_bytes(b"a2").sha512);
+            assert_eq!(new.env, a.env);
+        }
+        assert_eq!(lockfile.entries[2], b);
+        assert!(lockfile.refresh_from(&lookup).await?.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,168 @@
+//! Models of the `modrinth.index.json` file of a modpack
+//!
+//! [documentation](https://support.modrinth.com/en/articles/8802351-modrinth-modpack-format-mrpack)
+
+use crate::structures::{
+    project::{Project, SideType},
+    version::{Hash, VersionFile},
+    Int,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use url::Url;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModpackIndex {
+    /// The version of the format, only `1` is supported
+    pub format_version: Int,
+    /// The game the modpack is made for, currently always `minecraft`
+    pub game: String,
+    /// A unique identifier for this specific version of the modpack
+    pub version_id: String,
+    pub name: String,
+    /// A short description of the modpack
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// The files to download when installing the modpack
+    pub files: Vec<ModpackFile>,
+    /// The versions of the game and loader the modpack needs
+    pub dependencies: HashMap<PackDependency, String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModpackFile {
+    /// The destination of the file, relative to the instance directory
+    pub path: String,
+    pub hashes: Hash,
+    /// Which sides the file is needed on, the file is required on both if not provided
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env: Option<FileEnv>,
+    /// URLs the file can be downloaded from, in order of preference
+    pub downloads: Vec<Url>,
+    /// The size of the file in bytes
+    pub file_size: Int,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileEnv {
+    pub client: EnvSupport,
+    pub server: EnvSupport,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum EnvSupport {
+    Required,
+    /// The user can choose whether to install the file
+    Optional,
+    Unsupported,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(from = "String", into = "String")]
+pub enum PackDependency {
+    Minecraft,
+    Forge,
+    NeoForge,
+    FabricLoader,
+    QuiltLoader,
+    /// A dependency not known to this crate, with the given key
+    Other(String),
+}
+
+impl PackDependency {
+    /// The key of the dependency in the index
+    pub fn as_str(&self) -> &str {
+        match self {
+            PackDependency::Minecraft => "minecraft",
+            PackDependency::Forge => "forge",
+            PackDependency::NeoForge => "neoforge",
+            PackDependency::FabricLoader => "fabric-loader",
+            PackDependency::QuiltLoader => "quilt-loader",
+            PackDependency::Other(key) => key,
+        }
+    }
+}
+
+impl From<String> for PackDependency {
+    fn from(key: String) -> Self {
+        match key.as_str() {
+            "minecraft" => PackDependency::Minecraft,
+            "forge" => PackDependency::Forge,
+            "neoforge" => PackDependency::NeoForge,
+            "fabric-loader" => PackDependency::FabricLoader,
+            "quilt-loader" => PackDependency::QuiltLoader,
+            _ => PackDependency::Other(key),
+        }
+    }
+}
+
+impl From<PackDependency> for String {
+    fn from(dependency: PackDependency) -> Self {
+        match dependency {
+            PackDependency::Other(key) => key,
+            dependency => dependency.as_str().to_owned(),
+        }
+    }
+}
+
+impl ModpackFile {
+    /// Download the version `file` to `path` in the instance, on the sides in `env`
+    pub fn new(file: &VersionFile, path: impl Into<String>, env: Option<FileEnv>) -> Self {
+        Self {
+            path: path.into(),
+            hashes: file.hashes.clone(),
+            env,
+            downloads: vec![file.url.clone()],
+            file_size: file.size,
+        }
+    }
+}
+
+impl FileEnv {
+    /// The sides that the files of `project` are needed on
+    pub fn from_project(project: &Project) -> Self {
+        Self {
+            client: project.client_side.into(),
+            server: project.server_side.into(),
+        }
+    }
+}
+
+impl From<SideType> for EnvSupport {
+    /// Sides that a project is not known to run on are treated as required
+    fn from(side: SideType) -> Self {
+        match side {
+            SideType::Required | SideType::Unknown => EnvSupport::Required,
+            SideType::Optional => EnvSupport::Optional,
+            SideType::Unsupported => EnvSupport::Unsupported,
+        }
+    }
+}
+
+/// A side that a modpack can be installed on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Side {
+    Client,
+    Server,
+}
+
+impl FileEnv {
+    /// Whether the file is needed on `side`
+    pub fn get(&self, side: Side) -> EnvSupport {
+        match side {
+            Side::Client => self.client,
+            Side::Server => self.server,
+        }
+    }
+}
+
+impl ModpackFile {
+    /// Whether the file should be installed on `side`, which includes optional files
+    pub fn supports(&self, side: Side) -> bool {
+        self.env
+            .is_none_or(|env| env.get(side) != EnvSupport::Unsupported)
+    }
+}
@@ -0,0 +1,321 @@
+//! Install modpacks into client or server instances
+
+use super::{
+    index::{ModpackFile, Side},
+    safe_path, Mrpack, OverridesFolder,
+};
+use crate::{
+    download::{Download, DownloadManager},
+    Error, Ferinth, Result,
+};
+use std::{
+    fs::File,
+    io::{Read, Seek},
+    marker::PhantomData,
+    path::Path,
+};
+use url::Url;
+
+/// The hosts that files in modpacks may be downloaded from
+pub const ALLOWED_HOSTS: &[&str] = &[
+    "cdn.modrinth.com",
+    "github.com",
+    "raw.githubusercontent.com",
+    "gitlab.com",
+];
+
+impl<R: Read + Seek + Send + 'static> Mrpack<R> {
+    /**
+    Install the modpack into the instance directory `dir` for `side`
+
+    The files that support `side`, including optional ones, are downloaded using `manager` and verified,
+    and then the overrides for `side` are extracted over them.
+    The files are downloaded without the token of `modrinth`, as they may be hosted by third parties.
+    Nothing is downloaded if any file would be downloaded from a host other than the [`ALLOWED_HOSTS`],
+    or over a scheme other than HTTPS, which fails with [`Error::DisallowedDownloadHost`].
+
+    ## Example
+    ```no_run
+    # use ferinth::{download::DownloadManager, mrpack::{index::Side, Mrpack}};
+    # tokio_test::block_on(async {
+    # let modrinth = ferinth::Ferinth::default();
+    Mrpack::open("my-pack.mrpack")?
+        .install(&modrinth, "server", Side::Server, &DownloadManager::new(8))
+        .await?;
+    # Ok::<_, ferinth::Error>(()) }).unwrap()
+    ```
+    */
+    pub async fn install<T>(
+        self,
+        modrinth: &Ferinth<T>,
+        dir: impl AsRef<Path>,
+        side: Side,
+        manager: &DownloadManager,
+    ) -> Result<()> {
+        self.install_from(
+            modrinth,
+            dir.as_ref(),
+            side,
+            manager,
+            "https",
+            ALLOWED_HOSTS,
+        )
+        .await
+    }
+
+    async fn install_from<T>(
+        mut self,
+        modrinth: &Ferinth<T>,
+        dir: &Path,
+        side: Side,
+        manager: &DownloadManager,
+        allowed_scheme: &str,
+        allowed_hosts: &[&str],
+    ) -> Result<()> {
+        let downloads = self
+            .index
+            .files
+            .iter()
+            .filter(|file| file.supports(side))
+            .map(|file| {
+                Ok(Download {
+                    url: download_url(file, allowed_scheme, allowed_hosts)?.clone(),
+                    destination: dir.join(safe_path(&file.path)?),
+                    size: file.file_size,
+                    hashes: file.hashes.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let anonymous = Ferinth::<()> {
+            client: modrinth.client.without_auth(),
+            auth: PhantomData,
+        };
+        for result in manager.download_all(&anonymous, downloads).await {
+            result?;
+        }
+        // Reading the archive and writing the files blocks
+        let dir = dir.to_owned();
+        tokio::task::spawn_blocking(move || self.extract_overrides(dir, side))
+            .await
+            .map_err(std::io::Error::from)?
+    }
+}
+
+impl<R: Read + Seek> Mrpack<R> {
+    /// Extract the files in the `overrides` folder, and then in the folder for `side`, into `dir`
+    pub fn extract_overrides(&mut self, dir: impl AsRef<Path>, side: Side) -> Result<()> {
+        let side_folder = match side {
+            Side::Client => OverridesFolder::Client,
+            Side::Server => OverridesFolder::Server,
+        };
+        for folder in [OverridesFolder::Both, side_folder] {
+            let files = self
+                .overrides()
+                .filter(|file| file.folder == folder)
+                .cloned()
+                .collect::<Vec<_>>();
+            for file in files {
+                let destination = dir.as_ref().join(&file.path);
+                if let Some(parent) = destination.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::io::copy(
+                    &mut self.read_override(&file)?,
+                    &mut File::create(destination)?,
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The first URL of `file` that uses `allowed_scheme` and is on one of the `allowed_hosts`
+fn download_url<'a>(
+    file: &'a ModpackFile,
+    allowed_scheme: &str,
+    allowed_hosts: &[&str],
+) -> Result<&'a Url> {
+    file.downloads
+        .iter()
+        .find(|url| {
+            url.scheme() == allowed_scheme
+                && url
+                    .host_str()
+                    .is_some_and(|host| allowed_hosts.contains(&host))
+        })
+        .ok_or_else(|| match file.downloads.first() {
+            Some(url) => Error::DisallowedDownloadHost(url.clone()),
+            None => Error::InvalidModpack(format!("{} has no download URLs", file.path)),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        hashing::hash_bytes,
+        mrpack::{
+            index::{EnvSupport, FileEnv, PackDependency},
+            writer::MrpackBuilder,
+        },
+        structures::version::Hash,
+        test_server::TestServer,
+    };
+    use std::io::Cursor;
+
+    fn file(url: Url, path: &str, data: &[u8], env: Option<FileEnv>) -> ModpackFile {
+        let hashes = hash_bytes(data);
+        ModpackFile {
+            path: path.to_owned(),
+            hashes: Hash {
+                sha512: hashes.sha512,
+                sha1: hashes.sha1,
+                others: Default::default(),
+            },
+            env,
+            downloads: vec![url],
+            file_size: data.len(),
+        }
+    }
+
+    #[tokio::test]
+    async fn server_side() -> Result<()> {
+        let server =
+            TestServer::serve(&[("lithium.jar", b"lithium"), ("sodium.jar", b"sodium")]).await;
+        let overrides = tempfile::tempdir()?;
+        for (folder, name, contents) in [
+            ("both", "config.json", "{}"),
+            ("client", "options.txt", "fov:90"),
+            ("server", "server.properties", "motd=Hi"),
+        ] {
+            std::fs::create_dir(overrides.path().join(folder))?;
+            std::fs::write(overrides.path().join(folder).join(name), contents)?;
+        }
+
+        let client_only = FileEnv {
+            client: EnvSupport::Required,
+            server: EnvSupport::Unsupported,
+        };
+        let archive = MrpackBuilder::new("Test Pack", "1.0.0")
+            .dependency(PackDependency::Minecraft, "1.20.1")
+            .file(file(
+                server.url.join("lithium.jar").unwrap(),
+                "mods/lithium.jar",
+                b"lithium",
+                None,
+            ))
+            .file(file(
+                server.url.join("sodium.jar").unwrap(),
+                "mods/sodium.jar",
+                b"sodium",
+                Some(client_only),
+            ))
+            .overrides(OverridesFolder::Both, overrides.path().join("both"))
+            .overrides(OverridesFolder::Client, overrides.path().join("client"))
+            .overrides(OverridesFolder::Server, overrides.path().join("server"))
+            .write(Cursor::new(vec![]))?;
+        let dir = tempfile::tempdir()?;
+        Mrpack::new(archive)?
+            .install_from(
+                &Ferinth::default(),
+                dir.path(),
+                Side::Server,
+                &DownloadManager::new(2),
+                "http",
+                &["127.0.0.1"],
+            )
+            .await?;
+
+        assert_eq!(
+            std::fs::read(dir.path().join("mods/lithium.jar"))?,
+            b"lithium"
+        );
+        assert!(!dir.path().join("mods/sodium.jar").exists());
+        assert!(dir.path().join("config.json").exists());
+        assert!(dir.path().join("server.properties").exists());
+        assert!(!dir.path().join("options.txt").exists());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn token_not_sent() -> Result<()> {
+        let server = TestServer::serve(&[("lithium.jar", b"lithium")]).await;
+        let archive = MrpackBuilder::new("Test Pack", "1.0.0")
+            .dependency(PackDependency::Minecraft, "1.20.1")
+            .file(file(
+                server.url.join("lithium.jar").unwrap(),
+                "mods/lithium.jar",
+                b"lithium",
+                None,
+            ))
+            .write(Cursor::new(vec![]))?;
+        let mut modrinth = Ferinth::<crate::Authenticated>::new("test", None, None, "secret")?;
+        // Even a file hosted by the API itself is downloaded without the token
+        modrinth.client.api_host = server.url.host_str().map(str::to_owned);
+        let dir = tempfile::tempdir()?;
+        Mrpack::new(archive)?
+            .install_from(
+                &modrinth,
+                dir.path(),
+                Side::Client,
+                &DownloadManager::new(2),
+                "http",
+                &["127.0.0.1"],
+            )
+            .await?;
+
+        let headers = server.headers.lock().unwrap();
+        assert_eq!(headers.len(), 1);
+        assert!(!headers[0].contains_key("authorization"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn disallowed_host() -> Result<()> {
+        let server = TestServer::serve(&[("lithium.jar", b"lithium")]).await;
+        let archive = MrpackBuilder::new("Test Pack", "1.0.0")
+            .dependency(PackDependency::Minecraft, "1.20.1")
+            .file(file(
+                server.url.join("lithium.jar").unwrap(),
+                "mods/lithium.jar",
+                b"lithium",
+                None,
+            ))
+            .write(Cursor::new(vec![]))?;
+        let dir = tempfile::tempdir()?;
+        let result = Mrpack::new(archive)?
+            .install(
+                &Ferinth::default(),
+                dir.path(),
+                Side::Client,
+                &DownloadManager::new(2),
+            )
+            .await;
+
+        assert!(matches!(result, Err(Error::DisallowedDownloadHost(_))));
+        assert!(!dir.path().join("mods").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn https_only() {
+        let sodium = "cdn.modrinth.com/data/AANobbMI/versions/xuWxRZPd/sodium.jar";
+        let mut file = file(
+            format!("http://{sodium}").parse().unwrap(),
+            "mods/sodium.jar",
+            b"sodium",
+            None,
+        );
+        assert!(matches!(
+            download_url(&file, "https", ALLOWED_HOSTS),
+            Err(Error::DisallowedDownloadHost(_))
+        ));
+
+        file.downloads
+            .push(format!("https://{sodium}").parse().unwrap());
+        assert_eq!(
+            download_url(&file, "https", ALLOWED_HOSTS).unwrap(),
+            &file.downloads[1]
+        );
+    }
+}
@@ -0,0 +1,314 @@
+/*!
+Read, write, and install Modrinth modpacks (`.mrpack` files)
+
+A modpack is a ZIP archive containing a `modrinth.index.json` file, which lists the files to download,
+and folders of files to copy into the instance directly:
+`overrides` for both sides, and `client-overrides` and `server-overrides` for only one side.
+
+[documentation](https://support.modrinth.com/en/articles/8802351-modrinth-modpack-format-mrpack)
+*/
+
+pub mod index;
+pub mod install;
+pub mod writer;
+
+use crate::{Error, Result};
+use index::ModpackIndex;
+use std::{
+    io::{Read, Seek},
+    path::{Component, Path, PathBuf},
+};
+use zip::ZipArchive;
+
+/// The path of the index file in the archive
+pub const INDEX_PATH: &str = "modrinth.index.json";
+
+/// A folder of files that are copied into the instance directly
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OverridesFolder {
+    /// `overrides`, copied on both sides
+    Both,
+    /// `client-overrides`, copied only on the client after [`Self::Both`]
+    Client,
+    /// `server-overrides`, copied only on the server after [`Self::Both`]
+    Server,
+}
+
+impl OverridesFolder {
+    /// The name of the folder in the archive
+    pub fn name(&self) -> &'static str {
+        match self {
+            OverridesFolder::Both => "overrides",
+            OverridesFolder::Client => "client-overrides",
+            OverridesFolder::Server => "server-overrides",
+        }
+    }
+}
+
+/// A file in one of the overrides folders
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Override {
+    pub folder: OverridesFolder,
+    /// The destination of the file, relative to the instance directory
+    pub path: PathBuf,
+    /// The index of the file in the archive
+    archive_index: usize,
+}
+
+/// A modpack archive with its parsed and validated index
+#[derive(Debug)]
+pub struct Mrpack<R> {
+    archive: ZipArchive<R>,
+    pub index: ModpackIndex,
+    overrides: Vec<Override>,
+}
+
+impl Mrpack<std::fs::File> {
+    /// Open and validate the modpack at `path`
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::new(std::fs::File::open(path)?)
+    }
+}
+
+impl<R: Read + Seek> Mrpack<R> {
+    /**
+    Read and validate the modpack archive in `reader`
+
+    Fails with [`Error::InvalidModpack`] if the index is missing or invalid,
+    and with [`Error::UnsafePath`] if a file would be written outside the instance directory.
+    */
+    pub fn new(reader: R) -> Result<Self> {
+        let mut archive = ZipArchive::new(reader)?;
+
+        let index: ModpackIndex = match archive.by_name(INDEX_PATH) {
+            Ok(file) => serde_json::from_reader(file)?,
+            Err(zip::result::ZipError::FileNotFound) => {
+                return Err(Error::InvalidModpack(format!("{INDEX_PATH} is missing")))
+            }
+            Err(err) => return Err(err.into()),
+        };
+        if index.format_version != 1 {
+            return Err(Error::InvalidModpack(format!(
+                "Unsupported format version {}",
+                index.format_version
+            )));
+        }
+        for file in &index.files {
+            safe_path(&file.path)?;
+            if file.downloads.is_empty() {
+                return Err(Error::InvalidModpack(format!(
+                    "{} has no download URLs",
+                    file.path
+                )));
+            }
+        }
+
+        let mut overrides = vec![];
+        for archive_index in 0..archive.len() {
+            let entry = archive.by_index_raw(archive_index)?;
+            if entry.is_dir() {
+                continue;
+            }
+            let name = entry.name();
+            for folder in [
+                OverridesFolder::Both,
+                OverridesFolder::Client,
+                OverridesFolder::Server,
+            ] {
+                if let Some(path) = name
+                    .strip_prefix(folder.name())
+                    .and_then(|path| path.strip_prefix('/'))
+                {
+                    overrides.push(Override {
+                        folder,
+                        path: safe_path(path)?,
+                        archive_index,
+                    });
+                }
+            }
+        }
+
+        Ok(Self {
+            archive,
+            index,
+            overrides,
+        })
+    }
+
+    /// The files in all the overrides folders
+    pub fn overrides(&self) -> impl Iterator<Item = &Override> {
+        self.overrides.iter()
+    }
+
+    /// Get a reader of the contents of `file`
+    pub fn read_override(&mut self, file: &Override) -> Result<impl Read + '_> {
+        Ok(self.archive.by_index(file.archive_index)?)
+    }
+}
+
+/**
+Convert `path` to a relative path that stays inside the directory it is joined to
+
+Fails with [`Error::UnsafePath`] if `path` is empty, absolute, contains `..`,
+or uses backslashes, which are separators on Windows.
+*/
+pub fn safe_path(path: &str) -> Result<PathBuf> {
+    let unsafe_path = || Error::UnsafePath(path.to_owned());
+    if path.is_empty() || path.contains('\\') {
+        return Err(unsafe_path());
+    }
+    let mut safe = PathBuf::new();
+    for component in Path::new(path).components() {
+        match component {
+            Component::Normal(component) => safe.push(component),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                return Err(unsafe_path())
+            }
+        }
+    }
+    if safe.as_os_str().is_empty() {
+        return Err(unsafe_path());
+    }
+    Ok(safe)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Write};
+    use zip::{write::SimpleFileOptions, ZipWriter};
+
+    fn index(path: &str) -> serde_json::Value {
+        serde_json::json!({
+            "formatVersion": 1,
+            "game": "minecraft",
+            "versionId": "1.0.0",
+            "name": "Test Pack",
+            "summary": "A modpack for testing",
+            "files": [{
+                "path": path,
+                "hashes": {
+                    "sha1": "a9993e364706816aba3e25717850c26c9cd0d89d",
+                    "sha512": "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f",
+                },
+                "env": { "client": "required", "server": "unsupported" },
+                "downloads": ["https://cdn.modrinth.com/data/AANobbMI/versions/xuWxRZPd/sodium.jar"],
+                "fileSize": 3,
+            }],
+            "dependencies": {
+                "minecraft": "1.20.1",
+                "fabric-loader": "0.15.0",
+                "liteloader": "1.0",
+                "rift": "1.0.4",
+            },
+        })
+    }
+
+    fn archive(index: Option<serde_json::Value>, entries: &[(&str, &[u8])]) -> Cursor<Vec<u8>> {
+        let mut writer = ZipWriter::new(Cursor::new(vec![]));
+        if let Some(index) = index {
+            writer
+                .start_file(INDEX_PATH, SimpleFileOptions::default())
+                .unwrap();
+            serde_json::to_writer(&mut writer, &index).unwrap();
+        }
+        for (name, data) in entries {
+            writer
+                .start_file(*name, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(data).unwrap();
+        }
+        let mut cursor = writer.finish().unwrap();
+        cursor.set_position(0);
+        cursor
+    }
+
+    #[test]
+    fn read() -> Result<()> {
+        let mut pack = Mrpack::new(archive(
+            Some(index("mods/sodium.jar")),
+            &[
+                ("overrides/config/sodium.json", b"{}"),
+                ("client-overrides/options.txt", b"fov:90"),
+                ("server-overrides/server.properties", b"motd=Hi"),
+                ("README.md", b"Not an override"),
+            ],
+        ))?;
+
+        assert_eq!(pack.index.name, "Test Pack");
+        assert_eq!(pack.index.files[0].file_size, 3);
+        assert_eq!(
+            pack.index.files[0].env.unwrap().server,
+            index::EnvSupport::Unsupported
+        );
+        assert_eq!(
+            pack.index.dependencies[&index::PackDependency::FabricLoader],
+            "0.15.0"
+        );
+        assert_eq!(
+            pack.index.dependencies[&index::PackDependency::Other("liteloader".into())],
+            "1.0"
+        );
+        // Unknown dependencies are written back unchanged
+        assert_eq!(
+            serde_json::to_value(&pack.index)?["dependencies"],
+            index("mods/sodium.jar")["dependencies"]
+        );
+
+        let overrides = pack.overrides().cloned().collect::<Vec<_>>();
+        assert_eq!(
+            overrides
+                .iter()
+                .map(|o| (o.folder, o.path.clone()))
+                .collect::<Vec<_>>(),
+            [
+                (OverridesFolder::Both, PathBuf::from("config/sodium.json")),
+                (OverridesFolder::Client, PathBuf::from("options.txt")),
+                (OverridesFolder::Server, PathBuf::from("server.properties")),
+            ]
+        );
+        let mut contents = String::new();
+        pack.read_override(&overrides[1])?
+            .read_to_string(&mut contents)?;
+        assert_eq!(contents, "fov:90");
+        Ok(())
+    }
+
+    #[test]
+    fn missing_index() {
+        assert!(matches!(
+            Mrpack::new(archive(None, &[])),
+            Err(Error::InvalidModpack(_))
+        ));
+    }
+
+    #[test]
+    fn reject_unsafe_file_path() {
+        for path in [
+            "../mods/evil.jar",
+            "/etc/passwd",
+            "mods\\..\\..\\evil.jar",
+            "",
+        ] {
+            assert!(
+                matches!(
+                    Mrpack::new(archive(Some(index(path)), &[])),
+                    Err(Error::UnsafePath(_))
+                ),
+                "{path:?} was accepted"
+            );
+        }
+    }
+
+    #[test]
+    fn reject_unsafe_override_path() {
+        assert!(matches!(
+            Mrpack::new(archive(
+                Some(index("mods/sodium.jar")),
+                &[("overrides/../../evil.sh", b"")],
+            )),
+            Err(Error::UnsafePath(_))
+        ));
+    }
+}
@@ -0,0 +1,317 @@
+//! Build modpacks from versions and override directories
+
+use super::{
+    index::{FileEnv, ModpackFile, ModpackIndex, PackDependency},
+    safe_path, OverridesFolder, INDEX_PATH,
+};
+use crate::{
+    structures::{
+        project::{Project, ProjectType},
+        version::Version,
+    },
+    Error, Ferinth, Result,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    fs::File,
+    io::{Seek, Write},
+    path::{Path, PathBuf},
+};
+use zip::{write::SimpleFileOptions, ZipWriter};
+
+/**
+Builds a modpack, filling in the details of files from the versions they come from
+
+## Example
+```no_run
+# use ferinth::mrpack::{index::PackDependency, writer::MrpackBuilder, OverridesFolder};
+# tokio_test::block_on(async {
+# let modrinth = ferinth::Ferinth::default();
+let versions = modrinth.version_get_multiple(&["xuWxRZPd", "sxWTUZpD"]).await?;
+MrpackBuilder::new("My Pack", "1.0.0")
+    .summary("Sodium and Lithium")
+    .dependency(PackDependency::Minecraft, "1.20.1")
+    .dependency(PackDependency::FabricLoader, "0.15.0")
+    .versions(&modrinth, &versions)
+    .await?
+    .overrides(OverridesFolder::Both, "pack/overrides")
+    .write_to_path("my-pack.mrpack")?;
+# Ok::<_, ferinth::Error>(()) }).unwrap()
+```
+*/
+#[derive(Debug, Clone)]
+pub struct MrpackBuilder {
+    index: ModpackIndex,
+    overrides: Vec<(OverridesFolder, PathBuf)>,
+}
+
+impl MrpackBuilder {
+    /// Start building version `version_id` of the modpack called `name`
+    pub fn new(name: impl Into<String>, version_id: impl Into<String>) -> Self {
+        Self {
+            index: ModpackIndex {
+                format_version: 1,
+                game: "minecraft".to_owned(),
+                version_id: version_id.into(),
+                name: name.into(),
+                summary: None,
+                files: vec![],
+                dependencies: HashMap::new(),
+            },
+            overrides: vec![],
+        }
+    }
+
+    /// Set the short description of the modpack
+    pub fn summary(mut self, summary: impl Into<String>) -> Self {
+        self.index.summary = Some(summary.into());
+        self
+    }
+
+    /// Require `version` of the game or of a loader
+    pub fn dependency(mut self, dependency: PackDependency, version: impl Into<String>) -> Self {
+        self.index.dependencies.insert(dependency, version.into());
+        self
+    }
+
+    /// Add a `file` to download when installing the modpack
+    pub fn file(mut self, file: ModpackFile) -> Self {
+        self.index.files.push(file);
+        self
+    }
+
+    /**
+    Add the primary file of `version` of `project`
+
+    The file is placed in the folder for the project's type, e.g. `mods` or `resourcepacks`,
+    and is installed on the sides that the project supports.
+    */
+    pub fn version(self, version: &Version, project: &Project) -> Result<Self> {
+        let file = version.primary_file().ok_or(Error::NoVersionFiles)?;
+        let folder = match project.project_type {
+            ProjectType::Resourcepack => "resourcepacks",
+            ProjectType::Shader => "shaderpacks",
+            _ => "mods",
+        };
+        Ok(self.file(ModpackFile::new(
+            file,
+            format!("{folder}/{}", file.filename),
+            Some(FileEnv::from_project(project)),
+        )))
+    }
+
+    /// Add the primary files of `versions`, getting their projects from `modrinth`
+    pub async fn versions<T>(
+        mut self,
+        modrinth: &Ferinth<T>,
+        versions: &[Version],
+    ) -> Result<Self> {
+        let project_ids = versions
+            .iter()
+            .map(|version| &version.project_id)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        let projects = if project_ids.is_empty() {
+            HashMap::new()
+        } else {
+            modrinth
+                .project_get_multiple(&project_ids)
+                .await?
+                .into_iter()
+                .map(|project| (project.id.clone(), project))
+                .collect()
+        };
+        for version in versions {
+            let project = projects
+                .get(&version.project_id)
+                .ok_or_else(|| Error::ProjectNotFound(version.project_id.clone()))?;
+            self = self.version(version, project)?;
+        }
+        Ok(self)
+    }
+
+    /// Copy the files in the directory at `path` into `folder` of the modpack
+    pub fn overrides(mut self, folder: OverridesFolder, path: impl Into<PathBuf>) -> Self {
+        self.overrides.push((folder, path.into()));
+        self
+    }
+
+    /// The index of the modpack built so far
+    pub fn index(&self) -> &ModpackIndex {
+        &self.index
+    }
+
+    /**
+    Write the modpack archive to `writer`, returning the writer
+
+    Fails with [`Error::InvalidModpack`] if no Minecraft version was set,
+    and with [`Error::UnsafePath`] if a file would be installed outside the instance directory.
+    */
+    pub fn write<W: Write + Seek>(&self, writer: W) -> Result<W> {
+        if !self
+            .index
+            .dependencies
+            .contains_key(&PackDependency::Minecraft)
+        {
+            return Err(Error::InvalidModpack(
+                "The Minecraft version was not set".to_owned(),
+            ));
+        }
+        for file in &self.index.files {
+            safe_path(&file.path)?;
+        }
+
+        let options = SimpleFileOptions::default();
+        let mut zip = ZipWriter::new(writer);
+        zip.start_file(INDEX_PATH, options)?;
+        serde_json::to_writer_pretty(&mut zip, &self.index)?;
+        for (folder, path) in &self.overrides {
+            add_dir(&mut zip, path, folder.name(), options)?;
+        }
+        Ok(zip.finish()?)
+    }
+
+    /// Write the modpack archive to a file at `path`
+    pub fn write_to_path(&self, path: impl AsRef<Path>) -> Result<()> {
+        self.write(File::create(path)?)?;
+        Ok(())
+    }
+}
+
+/// Recursively add the files in the directory at `path` to `zip` under `prefix`
+fn add_dir<W: Write + Seek>(
+    zip: &mut ZipWriter<W>,
+    path: &Path,
+    prefix: &str,
+    options: SimpleFileOptions,
+) -> Result<()> {
+    let mut entries = std::fs::read_dir(path)?.collect::<std::io::Result<Vec<_>>>()?;
+    // Sort so that the archive is the same every time
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        let name = entry.file_name();
+        let name = format!(
+            "{prefix}/{}",
+            name.to_str()
+                .ok_or_else(|| Error::UnsafePath(name.to_string_lossy().into_owned()))?
+        );
+        if entry.file_type()?.is_dir() {
+            add_dir(zip, &entry.path(), &name, options)?;
+        } else {
+            zip.start_file(name, options)?;
+            std::io::copy(&mut File::open(entry.path())?, zip)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        hashing::hash_bytes,
+        mrpack::{index::EnvSupport, Mrpack},
+        structures::version::{Hash, VersionFile},
+    };
+    use std::io::{Cursor, Read};
+
+    fn version_file() -> VersionFile {
+        let hashes = hash_bytes(b"abc");
+        VersionFile {
+            hashes: Hash {
+                sha512: hashes.sha512,
+                sha1: hashes.sha1,
+                others: Default::default(),
+            },
+            url: "https://cdn.modrinth.com/data/AANobbMI/versions/xuWxRZPd/sodium.jar"
+                .parse()
+                .unwrap(),
+            filename: "sodium.jar".to_owned(),
+            primary: true,
+            size: 3,
+            file_type: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn no_versions() -> Result<()> {
+        // Nothing is requested, so this works without the network
+        let builder = MrpackBuilder::new("Test Pack", "1.0.0")
+            .versions(&Ferinth::default(), &[])
+            .await?;
+        assert!(builder.index().files.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::create_dir_all(dir.path().join("config/sodium"))?;
+        std::fs::write(dir.path().join("config/sodium/options.json"), "{}")?;
+        std::fs::write(dir.path().join("options.txt"), "fov:90")?;
+
+        let env = FileEnv {
+            client: EnvSupport::Required,
+            server: EnvSupport::Unsupported,
+        };
+        let archive = MrpackBuilder::new("Test Pack", "1.0.0")
+            .dependency(PackDependency::Minecraft, "1.20.1")
+            .dependency(PackDependency::FabricLoader, "0.15.0")
+            .file(ModpackFile::new(
+                &version_file(),
+                "mods/sodium.jar",
+                Some(env),
+            ))
+            .overrides(OverridesFolder::Client, dir.path())
+            .write(Cursor::new(vec![]))?;
+
+        let mut pack = Mrpack::new(archive)?;
+        let file = &pack.index.files[0];
+        assert_eq!(file.path, "mods/sodium.jar");
+        assert_eq!(file.file_size, 3);
+        assert_eq!(file.hashes.sha1, version_file().hashes.sha1);
+        assert_eq!(file.env, Some(env));
+        assert_eq!(file.downloads, [version_file().url]);
+        assert_eq!(pack.index.dependencies.len(), 2);
+
+        let overrides = pack.overrides().cloned().collect::<Vec<_>>();
+        assert_eq!(
+            overrides
+                .iter()
+                .map(|o| (o.folder, o.path.clone()))
+                .collect::<Vec<_>>(),
+            [
+                (
+                    OverridesFolder::Client,
+                    PathBuf::from("config/sodium/options.json")
+                ),
+                (OverridesFolder::Client, PathBuf::from("options.txt")),
+            ]
+        );
+        let mut contents = String::new();
+        pack.read_override(&overrides[1])?
+            .read_to_string(&mut contents)?;
+        assert_eq!(contents, "fov:90");
+        Ok(())
+    }
+
+    #[test]
+    fn requires_minecraft() {
+        assert!(matches!(
+            MrpackBuilder::new("Test Pack", "1.0.0").write(Cursor::new(vec![])),
+            Err(Error::InvalidModpack(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_unsafe_path() {
+        assert!(matches!(
+            MrpackBuilder::new("Test Pack", "1.0.0")
+                .dependency(PackDependency::Minecraft, "1.20.1")
+                .file(ModpackFile::new(&version_file(), "../sodium.jar", None))
+                .write(Cursor::new(vec![])),
+            Err(Error::UnsafePath(_))
+        ));
+    }
+}
@@ -0,0 +1,280 @@
+//! Parse links to modrinth.com and cdn.modrinth.com into typed references, and resolve them
+
+use crate::{
+    structures::{
+        id::{CollectionId, OrganizationRef, ProjectId, ProjectRef, UserRef, VersionId},
+        project::{Project, ProjectType},
+        user::User,
+        version::{Version, VersionFile},
+    },
+    v3::structures::{collection::Collection, organization::Organization},
+    Error, Ferinth, Result,
+};
+use std::str::FromStr;
+use url::Url;
+
+/// Something on Modrinth that a link points to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reference {
+    /// The project of the ID or slug `project`,
+    /// and its type if the link included one, e.g. `https://modrinth.com/mod/sodium`
+    Project {
+        project: ProjectRef,
+        project_type: Option<ProjectType>,
+    },
+    /// The version of the version number or ID `version`, of the project of the ID or slug `project`,
+    /// e.g. `https://modrinth.com/mod/sodium/version/mc1.20.1-0.5.3`
+    Version {
+        project: ProjectRef,
+        version: String,
+    },
+    /// The user of the ID or username, e.g. `https://modrinth.com/user/jellysquid3`
+    User(UserRef),
+    /// The organization of the ID or slug, e.g. `https://modrinth.com/organization/caffeinemc`
+    Organization(OrganizationRef),
+    /// The collection of the ID, e.g. `https://modrinth.com/collection/XXXXXXXX`
+    Collection(CollectionId),
+    /// A file of a version, e.g. `https://cdn.modrinth.com/data/AANobbMI/versions/xuWxRZPd/sodium-fabric.jar`
+    File {
+        project_id: ProjectId,
+        version_id: VersionId,
+        file_name: String,
+    },
+}
+
+/// What a [`Reference`] resolved to
+#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
+pub enum Resource {
+    Project(Project),
+    Version(Version),
+    User(User),
+    Organization(Organization),
+    Collection(Collection),
+    /// The `file` of `version`
+    File {
+        version: Version,
+        file: VersionFile,
+    },
+}
+
+impl Reference {
+    /// Parse a link to modrinth.com or cdn.modrinth.com
+    pub fn from_url(url: &Url) -> Result<Self> {
+        let invalid = || Error::InvalidReference(url.to_string());
+        let segments = url
+            .path_segments()
+            .ok_or_else(invalid)?
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| {
+                // Decode percent-encoded characters, but keep `+`s as they are in paths
+                let segment = segment.replace('+', "%2B");
+                url::form_urlencoded::parse(format!("s={segment}").as_bytes())
+                    .next()
+                    .map(|(_, segment)| segment.into_owned())
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>();
+        let segments = segments.iter().map(String::as_str).collect::<Vec<_>>();
+
+        match url.host_str() {
+            Some("modrinth.com" | "www.modrinth.com") => {}
+            Some("cdn.modrinth.com") => {
+                return match segments[..] {
+                    ["data", project_id, "versions", version_id, file_name] => Ok(Self::File {
+                        project_id: project_id.parse().map_err(|_| invalid())?,
+                        version_id: version_id.parse().map_err(|_| invalid())?,
+                        file_name: file_name.to_owned(),
+                    }),
+                    _ => Err(invalid()),
+                }
+            }
+            _ => return Err(invalid()),
+        }
+
+        Ok(match segments[..] {
+            ["user", user, ..] => Self::User(user.parse().map_err(|_| invalid())?),
+            ["organization", organization, ..] => {
+                Self::Organization(organization.parse().map_err(|_| invalid())?)
+            }
+            ["collection", collection, ..] => {
+                Self::Collection(collection.parse().map_err(|_| invalid())?)
+            }
+            [project_type, project, ref rest @ ..] => {
+                let project = project.parse::<ProjectRef>().map_err(|_| invalid())?;
+                let project_type = match project_type {
+                    "project" => None,
+                    "mod" => Some(ProjectType::Mod),
+                    "modpack" => Some(ProjectType::Modpack),
+                    "resourcepack" => Some(ProjectType::Resourcepack),
+                    "shader" => Some(ProjectType::Shader),
+                    "plugin" => Some(ProjectType::Plugin),
+                    "datapack" => Some(ProjectType::Datapack),
+                    _ => return Err(invalid()),
+                };
+                match rest {
+                    ["version", version, ..] => Self::Version {
+                        project,
+                        version: version.to_string(),
+                    },
+                    _ => Self::Project {
+                        project,
+                        project_type,
+                    },
+                }
+            }
+            _ => return Err(invalid()),
+        })
+    }
+
+    /**
+    Get what the reference points to using the matching API call
+
+    ## Example
+    ```rust
+    # use ferinth::reference::{Reference, Resource};
+    # tokio_test::block_on(async {
+    # let modrinth = ferinth::testing::doc_client();
+    let reference: Reference = "https://modrinth.com/mod/sodium".parse()?;
+    let Resource::Project(sodium) = reference.resolve(&modrinth).await? else {
+        unreachable!()
+    };
+    assert_eq!(sodium.title, "Sodium");
+    # Ok::<_, ferinth::Error>(()) }).unwrap()
+    ```
+    */
+    pub async fn resolve<T>(&self, modrinth: &Ferinth<T>) -> Result<Resource> {
+        Ok(match self {
+            Self::Project { project, .. } => {
+                Resource::Project(modrinth.project_get(project).await?)
+            }
+            Self::Version { project, version } => {
+                Resource::Version(modrinth.version_get_from_number(project, version).await?)
+            }
+            Self::User(user) => Resource::User(modrinth.user_get(user).await?),
+            Self::Organization(organization) => {
+                Resource::Organization(modrinth.v3().organization_get(organization).await?)
+            }
+            Self::Collection(collection) => {
+                Resource::Collection(modrinth.v3().collection_get(collection).await?)
+            }
+            Self::File {
+                version_id,
+                file_name,
+                ..
+            } => {
+                let version = modrinth.version_get(version_id).await?;
+                let file = version
+                    .files
+                    .iter()
+                    .find(|file| file.filename == *file_name)
+                    .cloned()
+                    .ok_or_else(|| Error::FileNotFound(file_name.clone()))?;
+                Resource::File { version, file }
+            }
+        })
+    }
+}
+
+impl FromStr for Reference {
+    type Err = Error;
+
+    /// Parse a link to modrinth.com or cdn.modrinth.com, with or without the scheme
+    fn from_str(s: &str) -> Result<Self> {
+        let url = match Url::parse(s) {
+            Ok(url) => url,
+            Err(_) => Url::parse(&format!("https://{s}"))
+                .map_err(|_| Error::InvalidReference(s.to_owned()))?,
+        };
+        Self::from_url(&url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Reference {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn projects_and_versions() {
+        assert_eq!(
+            parse("https://modrinth.com/mod/sodium"),
+            Reference::Project {
+                project: "sodium".parse().unwrap(),
+                project_type: Some(ProjectType::Mod),
+            }
+        );
+        assert_eq!(
+            parse("modrinth.com/project/AANobbMI/versions?l=fabric"),
+            Reference::Project {
+                project: "AANobbMI".parse().unwrap(),
+                project_type: None,
+            }
+        );
+        assert_eq!(
+            parse("https://modrinth.com/mod/sodium/version/mc1.20.1-0.5.3"),
+            Reference::Version {
+                project: "sodium".parse().unwrap(),
+                version: "mc1.20.1-0.5.3".into(),
+            }
+        );
+        assert_eq!(
+            parse("https://modrinth.com/mod/fabric-api/version/0.92.2+1.20.1"),
+            Reference::Version {
+                project: "fabric-api".parse().unwrap(),
+                version: "0.92.2+1.20.1".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn users_organizations_and_collections() {
+        assert_eq!(
+            parse("https://modrinth.com/user/jellysquid3/"),
+            Reference::User("jellysquid3".parse().unwrap())
+        );
+        assert_eq!(
+            parse("https://www.modrinth.com/organization/caffeinemc"),
+            Reference::Organization("caffeinemc".parse().unwrap())
+        );
+        assert_eq!(
+            parse("https://modrinth.com/collection/Xh3OWH1e"),
+            Reference::Collection("Xh3OWH1e".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn files() {
+        assert_eq!(
+            parse("https://cdn.modrinth.com/data/AANobbMI/versions/xuWxRZPd/sodium%20fabric.jar"),
+            Reference::File {
+                project_id: "AANobbMI".parse().unwrap(),
+                version_id: "xuWxRZPd".parse().unwrap(),
+                file_name: "sodium fabric.jar".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        for invalid in [
+            "https://example.com/mod/sodium",
+            "https://modrinth.com/",
+            "https://modrinth.com/mods",
+            "https://modrinth.com/software/sodium",
+            "https://cdn.modrinth.com/data/AANobbMI",
+            "https://cdn.modrinth.com/data/sodium-fabric/versions/xuWxRZPd/sodium.jar",
+        ] {
+            assert!(
+                matches!(
+                    invalid.parse::<Reference>(),
+                    Err(Error::InvalidReference(_))
+                ),
+                "{invalid:?} was accepted"
+            );
+        }
+    }
+}
@@ -1,6 +1,141 @@
-use crate::Result;
-use reqwest::{RequestBuilder, Response, StatusCode};
-use serde::de::DeserializeOwned;
+use crate::{
+    cache::{Cache, CachePolicy, Endpoint, OfflineMode},
+    Error, Result, BASE_URL,
+};
+use reqwest::{
+    header::{HeaderName, HeaderValue, AUTHORIZATION},
+    Body, Method, Response, StatusCode,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use url::Url;
+
+/// The HTTP client of a container, which caches the responses of `GET` requests and hash lookups if it has a cache
+#[derive(Debug, Clone)]
+pub(crate) struct Client {
+    http: reqwest::Client,
+    pub(crate) cache: Option<Arc<Cache>>,
+    pub(crate) cache_policy: CachePolicy,
+    pub(crate) offline_mode: OfflineMode,
+    /// Whether stale responses are used outside of [`track_freshness`](crate::cache::track_freshness)
+    pub(crate) untracked_stale: bool,
+    pub(crate) transport: Transport,
+    /// The authorisation token sent with requests to the API, and a digest of it, which is part of the cache key
+    auth: Option<(HeaderValue, String)>,
+    /// The host of the API, which is the only host the token is sent to
+    pub(crate) api_host: Option<String>,
+}
+
+impl Client {
+    pub fn new(http: reqwest::Client) -> Self {
+        Self {
+            http,
+            auth: None,
+            api_host: BASE_URL.host_str().map(str::to_owned),
+            cache: None,
+            cache_policy: CachePolicy::default(),
+            offline_mode: OfflineMode::default(),
+            untracked_stale: false,
+            transport: Transport::default(),
+        }
+    }
+
+    /// Send the authorisation `token` with requests to the API,
+    /// and cache their responses separately from those of other tokens
+    pub fn authenticated(mut self, mut token: HeaderValue) -> Self {
+        token.set_sensitive(true);
+        let digest = format!("{:x}", Sha256::digest(token.as_bytes()));
+        self.auth = Some((token, digest));
+        self
+    }
+
+    /// A copy of this client that never sends the authorisation token
+    pub fn without_auth(&self) -> Self {
+        Self {
+            auth: None,
+            ..self.clone()
+        }
+    }
+
+    fn request(&self, method: Method, url: Url) -> RequestBuilder {
+        let mut inner = self.http.request(method.clone(), url.clone());
+        // Never send the token to other hosts, such as those that files are downloaded from
+        let auth = match &self.auth {
+            Some((token, digest)) if url.host_str() == self.api_host.as_deref() => {
+                inner = inner.header(AUTHORIZATION, token);
+                Some(digest.clone())
+            }
+            _ => None,
+        };
+        RequestBuilder {
+            inner,
+            method,
+            url,
+            json: None,
+            auth,
+            cache: self.cache.clone(),
+            cache_policy: self.cache_policy,
+            offline_mode: self.offline_mode,
+            untracked_stale: self.untracked_stale,
+            transport: self.transport.clone(),
+        }
+    }
+
+    pub fn get(&self, url: Url) -> RequestBuilder {
+        self.request(Method::GET, url)
+    }
+
+    pub fn post(&self, url: Url) -> RequestBuilder {
+        self.request(Method::POST, url)
+    }
+
+    pub fn patch(&self, url: Url) -> RequestBuilder {
+        self.request(Method::PATCH, url)
+    }
+
+    pub fn delete(&self, url: Url) -> RequestBuilder {
+        self.request(Method::DELETE, url)
+    }
+}
+
+/// A request that will be sent by a [`Client`]
+pub(crate) struct RequestBuilder {
+    pub(crate) inner: reqwest::RequestBuilder,
+    pub(crate) method: Method,
+    pub(crate) url: Url,
+    /// The JSON body of the request, which is part of the cache key of hash lookups
+    pub(crate) json: Option<String>,
+    /// A digest of the authorisation token sent with the request
+    pub(crate) auth: Option<String>,
+    pub(crate) cache: Option<Arc<Cache>>,
+    pub(crate) cache_policy: CachePolicy,
+    pub(crate) offline_mode: OfflineMode,
+    pub(crate) untracked_stale: bool,
+    pub(crate) transport: Transport,
+}
+
+impl RequestBuilder {
+    pub fn header<V>(mut self, name: HeaderName, value: V) -> Self
+    where
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<http::Error>,
+    {
+        self.inner = self.inner.header(name, value);
+        self
+    }
+
+    pub fn body(mut self, body: impl Into<Body>) -> Self {
+        self.inner = self.inner.body(body);
+        self
+    }
+
+    pub fn json<T: Serialize + ?Sized>(mut self, json: &T) -> Self {
+        self.inner = self.inner.json(json);
+        self.json = serde_json::to_string(json).ok();
+        self
+    }
+}
 
 pub(crate) trait RequestBuilderCustomSend {
     /// Build and send `self`, and return the response
@@ -10,14 +145,56 @@ pub(crate) trait RequestBuilderCustomSend {
     async fn custom_send_json<T: DeserializeOwned>(self) -> Result<T>;
 }
 
+/// How requests reach the server
+#[derive(Debug, Clone, Default)]
+pub(crate) enum Transport {
+    #[default]
+    Network,
+    /// Record responses to, or replay them from, fixtures
+    #[cfg(feature = "testing")]
+    Fixtures(Arc<crate::testing::Fixtures>),
+}
+
+impl Transport {
+    /// Build and send `request`, and return the response
+    pub async fn send(&self, request: reqwest::RequestBuilder) -> Result<Response> {
+        let response = match self {
+            Self::Network => request.send().await?,
+            #[cfg(feature = "testing")]
+            Self::Fixtures(fixtures) => fixtures.send(request).await?,
+        };
+        Ok(check_rate_limit(response)?.error_for_status()?)
+    }
+}
+
 impl RequestBuilderCustomSend for RequestBuilder {
     async fn custom_send(self) -> Result<Response> {
-        Ok(check_rate_limit(self.send().await?)?.error_for_status()?)
+        if self.offline_mode == OfflineMode::Always {
+            return Err(Error::Offline(self.url));
+        }
+        let response = self.transport.send(self.inner).await?;
+        if let Some(cache) = &self.cache {
+            if self.method != Method::GET
+                && Endpoint::from_request(&self.method, &self.url).is_none()
+            {
+                cache.invalidate_mutated().await;
+            }
+        }
+        Ok(response)
     }
 
     async fn custom_send_json<T: DeserializeOwned>(self) -> Result<T> {
-        let bytes = self.custom_send().await?.bytes().await?;
-        Ok(serde_json::from_slice(&bytes)?)
+        let endpoint = Endpoint::from_request(&self.method, &self.url);
+        match (self.cache.clone(), endpoint) {
+            (Some(cache), Some(endpoint)) => {
+                let body = cache.fetch(self, endpoint).await?;
+                Ok(serde_json::from_str(&body)?)
+            }
+            _ => {
+                let bytes = self.custom_send().await?.bytes().await?;
+                Ok(serde_json::from_slice(&bytes)?)
+            }
+        }
     }
 }
 
@@ -42,3 +219,37 @@ fn check_rate_limit(response: Response) -> Result<Response> {
         Ok(response)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::API_BASE_URL;
+
+    #[test]
+    fn token_only_sent_to_api() {
+        let client =
+            Client::new(reqwest::Client::new()).authenticated(HeaderValue::from_static("secret"));
+        let authorization = |url: &str| {
+            let request = client.get(Url::parse(url).unwrap());
+            let auth = request.auth.clone();
+            let header = request
+                .inner
+                .build()
+                .unwrap()
+                .headers()
+                .get(AUTHORIZATION)
+                .cloned();
+            (header, auth)
+        };
+
+        let (header, auth) = authorization(API_BASE_URL.join("user").unwrap().as_str());
+        assert_eq!(header.unwrap(), "secret");
+        assert!(auth.is_some());
+        for url in [
+            "https://cdn.modrinth.com/data/AANobbMI/versions/yaoBL9D9/sodium.jar",
+            "https://github.com/CaffeineMC/sodium/releases/download/sodium.jar",
+        ] {
+            assert_eq!(authorization(url), (None, None));
+        }
+    }
+}
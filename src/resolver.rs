@@ -0,0 +1,667 @@
+//! Resolve the transitive dependencies of projects into a compatible set of versions
+
+use crate::{
+    api_calls::into_ids,
+    structures::{
+        id::{IntoId, ProjectId, ProjectRef, VersionId},
+        version::{DependencyType, Version},
+    },
+    Ferinth, Result,
+};
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, HashMap, VecDeque},
+    fmt,
+};
+
+/// Why a version was chosen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reason {
+    /// The version is the best of the `candidates` compatible versions of a requested project
+    Requested { candidates: usize },
+    /// The version of `dependent` depends on exactly this version
+    Pinned { dependent: VersionId },
+    /// The version is the best of the `candidates` compatible versions of a project
+    /// that the version of `dependent` depends on
+    Dependency {
+        dependent: VersionId,
+        dependency_type: DependencyType,
+        candidates: usize,
+    },
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Reason::Requested { candidates } => write!(
+                f,
+                "requested, and the best of {candidates} compatible versions"
+            ),
+            Reason::Pinned { dependent } => {
+                write!(f, "version {dependent} depends on exactly this version")
+            }
+            Reason::Dependency {
+                dependent,
+                dependency_type,
+                candidates,
+            } => write!(
+                f,
+                "{} by version {dependent}, and the best of {candidates} compatible versions",
+                match dependency_type {
+                    DependencyType::Optional => "optionally needed",
+                    _ => "needed",
+                }
+            ),
+        }
+    }
+}
+
+/// Why a set of projects could not be resolved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Conflict {
+    /// No version of the project of `project_id` is compatible,
+    /// and it was requested or is required by the version of `required_by`
+    NoCompatibleVersion {
+        project_id: ProjectRef,
+        required_by: Option<VersionId>,
+    },
+    /// The version of `version_id`, which the version of `required_by` depends on exactly,
+    /// does not support any of the loaders or game versions
+    UnsupportedPinnedVersion {
+        version_id: VersionId,
+        required_by: VersionId,
+    },
+    /// Both `versions` of the project of `project_id` need to be installed
+    DifferentVersions {
+        project_id: ProjectId,
+        versions: [VersionId; 2],
+    },
+    /// The version of `version_id` is incompatible with the resolved version of `incompatible`
+    Incompatible {
+        version_id: VersionId,
+        incompatible: VersionId,
+    },
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Conflict::NoCompatibleVersion {
+                project_id,
+                required_by: None,
+            } => write!(f, "No version of {project_id} is compatible"),
+            Conflict::NoCompatibleVersion {
+                project_id,
+                required_by: Some(required_by),
+            } => write!(
+                f,
+                "No version of {project_id}, which is required by version {required_by}, is compatible"
+            ),
+            Conflict::UnsupportedPinnedVersion {
+                version_id,
+                required_by,
+            } => write!(
+                f,
+                "Version {version_id}, which version {required_by} depends on exactly, is not compatible"
+            ),
+            Conflict::DifferentVersions {
+                project_id,
+                versions: [first, second],
+            } => write!(
+                f,
+                "Both versions {first} and {second} of {project_id} are needed"
+            ),
+            Conflict::Incompatible {
+                version_id,
+                incompatible,
+            } => write!(
+                f,
+                "Version {version_id} is incompatible with version {incompatible}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Conflict {}
+
+/// A version chosen by the resolver, and why it was chosen
+#[derive(Debug, Clone)]
+pub struct Resolved {
+    pub version: Version,
+    pub reason: Reason,
+}
+
+/// The result of resolving a set of projects
+#[derive(Debug, Clone, Default)]
+pub struct Resolution {
+    /// The chosen versions, with the requested ones first followed by their dependencies
+    pub versions: Vec<Resolved>,
+    /// The dependency cycles that were found, as the IDs of the projects in each cycle,
+    /// starting and ending with the same project
+    pub cycles: Vec<Vec<ProjectId>>,
+}
+
+/// Where the resolver gets versions from
+trait VersionSource {
+    /// The versions of the project of `project_id` that match the filters of `resolver`
+    async fn versions(&self, project_id: &ProjectRef, resolver: &Resolver) -> Result<Vec<Version>>;
+    async fn version(&self, version_id: &VersionId) -> Result<Version>;
+}
+
+impl<T> VersionSource for Ferinth<T> {
+    async fn versions(&self, project_id: &ProjectRef, resolver: &Resolver) -> Result<Vec<Version>> {
+        let loaders = resolver
+            .loaders
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        let game_versions = resolver
+            .game_versions
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        self.version_list_filtered(
+            project_id,
+            (!loaders.is_empty()).then_some(&loaders[..]),
+            (!game_versions.is_empty()).then_some(&game_versions[..]),
+            None,
+        )
+        .await
+    }
+
+    async fn version(&self, version_id: &VersionId) -> Result<Version> {
+        self.version_get(version_id).await
+    }
+}
+
+/**
+Resolves projects and their transitive dependencies into one version per project,
+choosing versions using the same filters as [`Ferinth::version_list_filtered`]
+
+The best version of a project is its newest release, or its newest beta or alpha if it has no releases.
+Dependencies on a specific version always use that version.
+
+Resolution fails with [`crate::Error::DependencyConflict`] if a required project has no compatible version,
+if a version that is required exactly is not compatible,
+if two different versions of a project are needed,
+or if a chosen version is incompatible with another chosen version.
+
+Versions are chosen in the order they are found, and are never revisited.
+So if a project has already been resolved to its best version,
+a later dependency on exactly another version of it fails with [`Conflict::DifferentVersions`],
+even if the pinned version would have satisfied every dependency on the project.
+
+## Example
+```no_run
+# use ferinth::resolver::Resolver;
+# tokio_test::block_on(async {
+# let modrinth = ferinth::Ferinth::default();
+let resolution = Resolver::new(vec!["fabric".into()], vec!["1.20.1".into()])
+    .resolve(&modrinth, &["iris"])
+    .await?;
+for resolved in &resolution.versions {
+    println!("{}: {}", resolved.version.name, resolved.reason);
+}
+# Ok::<_, ferinth::Error>(()) }).unwrap()
+```
+*/
+#[derive(Debug, Clone)]
+pub struct Resolver {
+    loaders: Vec<String>,
+    game_versions: Vec<String>,
+    include_optional: bool,
+}
+
+impl Resolver {
+    /// Resolve versions that support any of the `loaders` and `game_versions`,
+    /// where empty lists match all versions
+    pub fn new(loaders: Vec<String>, game_versions: Vec<String>) -> Self {
+        Self {
+            loaders,
+            game_versions,
+            include_optional: false,
+        }
+    }
+
+    /// Whether to resolve optional dependencies too, which are skipped if they have no compatible version
+    pub fn include_optional(mut self, include_optional: bool) -> Self {
+        self.include_optional = include_optional;
+        self
+    }
+
+    /// Resolve the projects of `project_ids` and their dependencies
+    pub async fn resolve<T>(
+        &self,
+        modrinth: &Ferinth<T>,
+        project_ids: &[impl IntoId<ProjectRef> + Clone],
+    ) -> Result<Resolution> {
+        self.resolve_from(modrinth, project_ids).await
+    }
+
+    /// Whether `version` matches the filters, in case the source did not apply them
+    fn matches(&self, version: &Version) -> bool {
+        (self.loaders.is_empty() || version.loaders.iter().any(|l| self.loaders.contains(l)))
+            && (self.game_versions.is_empty()
+                || version
+                    .game_versions
+                    .iter()
+                    .any(|v| self.game_versions.contains(v)))
+    }
+
+    /// The best compatible version of the project of `project_id`,
+    /// and the number of compatible versions it was chosen from
+    async fn best_version(
+        &self,
+        source: &impl VersionSource,
+        project_id: &ProjectRef,
+    ) -> Result<Option<(Version, usize)>> {
+        let versions = source
+            .versions(project_id, self)
+            .await?
+            .into_iter()
+            .filter(|version| self.matches(version))
+            .collect::<Vec<_>>();
+        let candidates = versions.len();
+        Ok(versions
+            .into_iter()
+            .max_by_key(|version| (Reverse(version.version_type), version.date_published))
+            .map(|version| (version, candidates)))
+    }
+
+    async fn resolve_from(
+        &self,
+        source: &impl VersionSource,
+        project_ids: &[impl IntoId<ProjectRef> + Clone],
+    ) -> Result<Resolution> {
+        let mut state = State::default();
+        for project_id in into_ids(project_ids)? {
+            let (version, candidates) =
+                self.best_version(source, &project_id)
+                    .await?
+                    .ok_or_else(|| Conflict::NoCompatibleVersion {
+                        project_id: project_id.clone(),
+                        required_by: None,
+                    })?;
+            state.add(version, Reason::Requested { candidates }, None)?;
+        }
+
+        let mut next = 0;
+        while next < state.resolution.versions.len() {
+            let dependent = &state.resolution.versions[next].version;
+            let (dependent_id, dependencies) =
+                (dependent.id.clone(), dependent.dependencies.clone());
+            for dependency in dependencies {
+                match dependency.dependency_type {
+                    DependencyType::Required => {}
+                    DependencyType::Optional if self.include_optional => {}
+                    _ => continue,
+                }
+
+                let (version, reason) = if let Some(version_id) = &dependency.version_id {
+                    if let Some(existing) = state.index_of_version(version_id) {
+                        state.found(existing, next);
+                        continue;
+                    }
+                    let version = source.version(version_id).await?;
+                    if !self.matches(&version) {
+                        if dependency.dependency_type == DependencyType::Optional {
+                            continue;
+                        }
+                        return Err(Conflict::UnsupportedPinnedVersion {
+                            version_id: version.id,
+                            required_by: dependent_id,
+                        }
+                        .into());
+                    }
+                    let reason = Reason::Pinned {
+                        dependent: dependent_id.clone(),
+                    };
+                    (version, reason)
+                } else if let Some(project_id) = &dependency.project_id {
+                    if let Some(&existing) = state.by_project.get(project_id) {
+                        state.found(existing, next);
+                        continue;
+                    }
+                    match self.best_version(source, &project_id.into()).await? {
+                        Some((version, candidates)) => (
+                            version,
+                            Reason::Dependency {
+                                dependent: dependent_id.clone(),
+                                dependency_type: dependency.dependency_type,
+                                candidates,
+                            },
+                        ),
+                        None if dependency.dependency_type == DependencyType::Optional => continue,
+                        None => {
+                            return Err(Conflict::NoCompatibleVersion {
+                                project_id: project_id.into(),
+                                required_by: Some(dependent_id),
+                            }
+                            .into())
+                        }
+                    }
+                } else {
+                    // Dependencies on files outside Modrinth cannot be resolved
+                    continue;
+                };
+                state.add(version, reason, Some(next))?;
+            }
+            next += 1;
+        }
+
+        for resolved in &state.resolution.versions {
+            for dependency in &resolved.version.dependencies {
+                if dependency.dependency_type != DependencyType::Incompatible {
+                    continue;
+                }
+                let incompatible = match (&dependency.version_id, &dependency.project_id) {
+                    (Some(version_id), _) => state.index_of_version(version_id),
+                    (None, Some(project_id)) => state.by_project.get(project_id).copied(),
+                    (None, None) => None,
+                };
+                if let Some(incompatible) = incompatible {
+                    return Err(Conflict::Incompatible {
+                        version_id: resolved.version.id.clone(),
+                        incompatible: state.resolution.versions[incompatible].version.id.clone(),
+                    }
+                    .into());
+                }
+            }
+        }
+        Ok(state.resolution)
+    }
+}
+
+#[derive(Default)]
+struct State {
+    resolution: Resolution,
+    /// The indices of the versions that each version depends on
+    dependencies: Vec<Vec<usize>>,
+    /// The index of the version of each project
+    by_project: HashMap<ProjectId, usize>,
+}
+
+impl State {
+    fn index_of_version(&self, version_id: &VersionId) -> Option<usize> {
+        self.resolution
+            .versions
+            .iter()
+            .position(|resolved| resolved.version.id == *version_id)
+    }
+
+    /// Add `version`, needed by the version at index `parent`, if its project has not been resolved yet
+    fn add(&mut self, version: Version, reason: Reason, parent: Option<usize>) -> Result<()> {
+        if let Some(&existing) = self.by_project.get(&version.project_id) {
+            let existing_id = &self.resolution.versions[existing].version.id;
+            if *existing_id != version.id {
+                return Err(Conflict::DifferentVersions {
+                    project_id: version.project_id,
+                    versions: [existing_id.clone(), version.id],
+                }
+                .into());
+            }
+            if let Some(parent) = parent {
+                self.found(existing, parent);
+            }
+            return Ok(());
+        }
+        self.by_project
+            .insert(version.project_id.clone(), self.resolution.versions.len());
+        self.resolution.versions.push(Resolved { version, reason });
+        self.dependencies.push(vec![]);
+        if let Some(parent) = parent {
+            self.dependencies[parent].push(self.resolution.versions.len() - 1);
+        }
+        Ok(())
+    }
+
+    /// Record that the version at index `dependent` depends on the one at index `existing`,
+    /// and the cycle this closes if `existing` already depends on `dependent`, directly or indirectly
+    fn found(&mut self, existing: usize, dependent: usize) {
+        if let Some(mut path) = self.path(existing, dependent) {
+            path.push(existing);
+            self.resolution.cycles.push(
+                path.into_iter()
+                    .map(|index| self.resolution.versions[index].version.project_id.clone())
+                    .collect(),
+            );
+        }
+        self.dependencies[dependent].push(existing);
+    }
+
+    /// The indices of the versions on the shortest path of dependencies from index `from` to index `to`
+    fn path(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        let mut previous = HashMap::from([(from, from)]);
+        let mut queue = VecDeque::from([from]);
+        while let Some(current) = queue.pop_front() {
+            if current == to {
+                let mut path = vec![current];
+                let mut current = current;
+                while current != from {
+                    current = previous[&current];
+                    path.push(current);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for &dependency in &self.dependencies[current] {
+                if let Entry::Vacant(entry) = previous.entry(dependency) {
+                    entry.insert(current);
+                    queue.push_back(dependency);
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_versions::version, Error};
+    use serde_json::json;
+
+    struct TestSource(Vec<Version>);
+
+    impl VersionSource for TestSource {
+        async fn versions(&self, project_id: &ProjectRef, _: &Resolver) -> Result<Vec<Version>> {
+            Ok(self
+                .0
+                .iter()
+                .filter(|version| version.project_id == project_id.as_str())
+                .cloned()
+                .collect())
+        }
+
+        async fn version(&self, version_id: &VersionId) -> Result<Version> {
+            Ok(self
+                .0
+                .iter()
+                .find(|version| version.id == *version_id)
+                .cloned()
+                .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::NotFound))?)
+        }
+    }
+
+    fn resolver() -> Resolver {
+        Resolver::new(vec!["fabric".into()], vec!["1.20.1".into()])
+    }
+
+    #[tokio::test]
+    async fn picks_best_versions() -> Result<()> {
+        let source = TestSource(vec![
+            version("aaaaaaa1", "AAAAAAAA")
+                .dependencies(json!([
+                    { "project_id": "BBBBBBBB", "dependency_type": "required" },
+                    { "project_id": "CCCCCCCC", "dependency_type": "optional" },
+                ]))
+                .build(),
+            version("bbbbbbb1", "BBBBBBBB").build(),
+            version("bbbbbbb2", "BBBBBBBB")
+                .day(2)
+                .version_type("beta")
+                .build(),
+            version("bbbbbbb3", "BBBBBBBB")
+                .day(3)
+                .game_version("1.19.4")
+                .build(),
+            version("ccccccc1", "CCCCCCCC").build(),
+        ]);
+
+        let resolution = resolver().resolve_from(&source, &["AAAAAAAA"]).await?;
+        let ids = resolution
+            .versions
+            .iter()
+            .map(|resolved| resolved.version.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, ["aaaaaaa1", "bbbbbbb1"]);
+        assert_eq!(
+            resolution.versions[1].reason,
+            Reason::Dependency {
+                dependent: "aaaaaaa1".parse().unwrap(),
+                dependency_type: DependencyType::Required,
+                candidates: 2,
+            }
+        );
+
+        let resolution = resolver()
+            .include_optional(true)
+            .resolve_from(&source, &["AAAAAAAA"])
+            .await?;
+        assert_eq!(resolution.versions.len(), 3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn pinned_and_cycles() -> Result<()> {
+        let source = TestSource(vec![
+            version("aaaaaaa1", "AAAAAAAA")
+                .dependencies(json!([
+                    { "version_id": "bbbbbbb1", "dependency_type": "required" },
+                ]))
+                .build(),
+            version("bbbbbbb1", "BBBBBBBB")
+                .dependencies(json!([
+                    { "project_id": "AAAAAAAA", "dependency_type": "required" },
+                ]))
+                .build(),
+            version("bbbbbbb2", "BBBBBBBB").day(2).build(),
+        ]);
+
+        let resolution = resolver().resolve_from(&source, &["AAAAAAAA"]).await?;
+        assert_eq!(resolution.versions[1].version.id, "bbbbbbb1");
+        assert_eq!(
+            resolution.versions[1].reason,
+            Reason::Pinned {
+                dependent: "aaaaaaa1".parse().unwrap()
+            }
+        );
+        assert_eq!(resolution.cycles, [["AAAAAAAA", "BBBBBBBB", "AAAAAAAA"]]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn conflicts() {
+        let source = TestSource(vec![
+            version("aaaaaaa1", "AAAAAAAA")
+                .dependencies(json!([
+                    { "project_id": "BBBBBBBB", "dependency_type": "required" },
+                ]))
+                .build(),
+            version("bbbbbbb1", "BBBBBBBB")
+                .dependencies(json!([
+                    { "project_id": "CCCCCCCC", "dependency_type": "incompatible" },
+                ]))
+                .build(),
+            version("ccccccc1", "CCCCCCCC").build(),
+            version("ddddddd1", "DDDDDDDD")
+                .game_version("1.19.4")
+                .build(),
+        ]);
+
+        assert!(matches!(
+            resolver().resolve_from(&source, &["AAAAAAAA", "CCCCCCCC"]).await,
+            Err(Error::DependencyConflict(Conflict::Incompatible { version_id, incompatible }))
+                if version_id == "bbbbbbb1" && incompatible == "ccccccc1"
+        ));
+        assert!(matches!(
+            resolver().resolve_from(&source, &["DDDDDDDD"]).await,
+            Err(Error::DependencyConflict(
+                Conflict::NoCompatibleVersion { .. }
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn later_pin_is_not_backtracked() {
+        // B is resolved to its best version for A before C's dependency on exactly b1 is found
+        let source = TestSource(vec![
+            version("aaaaaaa1", "AAAAAAAA")
+                .dependencies(json!([
+                    { "project_id": "BBBBBBBB", "dependency_type": "required" },
+                    { "project_id": "CCCCCCCC", "dependency_type": "required" },
+                ]))
+                .build(),
+            version("bbbbbbb1", "BBBBBBBB").build(),
+            version("bbbbbbb2", "BBBBBBBB").day(2).build(),
+            version("ccccccc1", "CCCCCCCC")
+                .dependencies(json!([
+                    { "version_id": "bbbbbbb1", "dependency_type": "required" },
+                ]))
+                .build(),
+        ]);
+
+        assert!(matches!(
+            resolver().resolve_from(&source, &["AAAAAAAA"]).await,
+            Err(Error::DependencyConflict(Conflict::DifferentVersions { project_id, versions: [first, second] }))
+                if project_id == "BBBBBBBB" && first == "bbbbbbb2" && second == "bbbbbbb1"
+        ));
+    }
+
+    #[tokio::test]
+    async fn unsupported_pinned_version() {
+        let source = TestSource(vec![
+            version("aaaaaaa1", "AAAAAAAA")
+                .dependencies(json!([
+                    { "version_id": "bbbbbbb1", "dependency_type": "required" },
+                ]))
+                .build(),
+            version("bbbbbbb1", "BBBBBBBB")
+                .game_version("1.19.4")
+                .build(),
+        ]);
+
+        assert!(matches!(
+            resolver().resolve_from(&source, &["AAAAAAAA"]).await,
+            Err(Error::DependencyConflict(Conflict::UnsupportedPinnedVersion { version_id, required_by }))
+                if version_id == "bbbbbbb1" && required_by == "aaaaaaa1"
+        ));
+    }
+
+    #[tokio::test]
+    async fn cycle_through_second_dependent() -> Result<()> {
+        // B is first needed by A, so the cycle C -> B -> C is only found through its second dependent
+        let source = TestSource(vec![
+            version("aaaaaaa1", "AAAAAAAA")
+                .dependencies(json!([
+                    { "project_id": "CCCCCCCC", "dependency_type": "required" },
+                    { "project_id": "BBBBBBBB", "dependency_type": "required" },
+                ]))
+                .build(),
+            version("bbbbbbb1", "BBBBBBBB")
+                .dependencies(json!([
+                    { "project_id": "CCCCCCCC", "dependency_type": "required" },
+                ]))
+                .build(),
+            version("ccccccc1", "CCCCCCCC")
+                .dependencies(json!([
+                    { "project_id": "BBBBBBBB", "dependency_type": "required" },
+                ]))
+                .build(),
+        ]);
+
+        let resolution = resolver().resolve_from(&source, &["AAAAAAAA"]).await?;
+        assert_eq!(resolution.cycles, [["CCCCCCCC", "BBBBBBBB", "CCCCCCCC"]]);
+        Ok(())
+    }
+}
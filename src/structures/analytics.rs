@@ -0,0 +1,78 @@
+//! Models related to analytics
+//!
+//! [documentation](https://docs.modrinth.com/api/operations/category/analytics)
+
+use super::*;
+use std::collections::{BTreeMap, HashMap};
+
+/// Data points of a metric over time, ordered by the start of their time bucket
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeSeries<T>(pub BTreeMap<UtcTime, T>);
+
+/// Time series of a metric, keyed by the ID of the project or version it belongs to
+pub type AnalyticsData<T> = HashMap<ID, TimeSeries<T>>;
+
+/// Totals of a metric, keyed by the ID of the project and then by the country code
+pub type CountryData = HashMap<ID, HashMap<String, Int>>;
+
+impl<T> std::ops::Deref for TimeSeries<T> {
+    type Target = BTreeMap<UtcTime, T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+// The time buckets are sent as UNIX timestamps
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for TimeSeries<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        <HashMap<i64, T>>::deserialize(de)?
+            .into_iter()
+            .map(|(timestamp, value)| {
+                chrono::DateTime::from_timestamp(timestamp, 0)
+                    .map(|time| (time, value))
+                    .ok_or_else(|| D::Error::custom(format!("invalid timestamp {timestamp}")))
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+impl<T: Serialize> Serialize for TimeSeries<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().map(|(time, value)| (time.timestamp(), value)))
+    }
+}
+
+/// The projects or versions to get analytics for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnalyticsItems<'a> {
+    /// Break the data down by project
+    Projects(&'a [ProjectId]),
+    /// Break the data down by version
+    Versions(&'a [VersionId]),
+}
+
+/// The size of the time buckets that data points are aggregated into
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    Hour,
+    Day,
+    Week,
+    /// A custom bucket size in minutes
+    Minutes(u32),
+}
+
+impl Resolution {
+    /// The size of the time buckets in minutes
+    pub fn minutes(&self) -> u32 {
+        match self {
+            Resolution::Hour => 60,
+            Resolution::Day => 60 * 24,
+            Resolution::Week => 60 * 24 * 7,
+            Resolution::Minutes(minutes) => *minutes,
+        }
+    }
+}
@@ -0,0 +1,319 @@
+//! Typed IDs, which are base 62 numbers stored as strings of [`ID_LENGTH`] characters
+//!
+//! IDs are validated when they are constructed, so a version ID cannot be used where a project ID is expected,
+//! and a slug cannot be used where an ID is expected.
+//! They can be converted from and compared with strings for convenience.
+//!
+//! ```rust
+//! # use ferinth::structures::{ProjectId, ProjectRef};
+//! let id: ProjectId = "AANobbMI".parse()?;
+//! assert_eq!(id, "AANobbMI");
+//! assert_eq!(ProjectId::from_u64(id.to_u64())?, id);
+//! assert!("not-an-id".parse::<ProjectId>().is_err());
+//! assert!("sodium".parse::<ProjectId>().is_err());
+//!
+//! // Projects can also be referred to by their slug
+//! let sodium: ProjectRef = "sodium".parse()?;
+//! # Ok::<_, ferinth::Error>(())
+//! ```
+
+use crate::{check_id_slug, Error, Result};
+use serde::{Deserialize, Serialize};
+
+/// The number of characters in an ID
+pub const ID_LENGTH: usize = 8;
+
+const BASE62_CHARS: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// Decode the base 62 `id` into the number it represents,
+/// or return `None` if it is empty, has characters outside base 62, or does not fit in a `u64`
+pub fn decode_base62(id: &str) -> Option<u64> {
+    if id.is_empty() {
+        return None;
+    }
+    id.bytes().try_fold(0_u64, |number, char| {
+        let digit = BASE62_CHARS.iter().position(|&c| c == char)? as u64;
+        number.checked_mul(62)?.checked_add(digit)
+    })
+}
+
+/// Encode `number` in base 62
+pub fn encode_base62(mut number: u64) -> String {
+    let mut chars = vec![];
+    loop {
+        chars.push(BASE62_CHARS[(number % 62) as usize]);
+        number /= 62;
+        if number == 0 {
+            break;
+        }
+    }
+    chars.reverse();
+    String::from_utf8(chars).expect("Base 62 characters are ASCII")
+}
+
+/// Implement string conversions and comparisons for a newtype over `String`
+macro_rules! string_newtype {
+    ($name:ident) => {
+        impl $name {
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl TryFrom<&str> for $name {
+            type Error = Error;
+            fn try_from(value: &str) -> Result<Self> {
+                Self::try_from(value.to_owned())
+            }
+        }
+
+        impl TryFrom<&String> for $name {
+            type Error = Error;
+            fn try_from(value: &String) -> Result<Self> {
+                Self::try_from(value.clone())
+            }
+        }
+
+        impl From<&$name> for $name {
+            fn from(value: &$name) -> Self {
+                value.clone()
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = Error;
+            fn from_str(s: &str) -> Result<Self> {
+                Self::try_from(s)
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl std::borrow::Borrow<str> for $name {
+            fn borrow(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl PartialEq<String> for $name {
+            fn eq(&self, other: &String) -> bool {
+                &self.0 == other
+            }
+        }
+    };
+}
+
+/// Define ID types that only accept base 62 numbers of [`ID_LENGTH`] characters
+macro_rules! ids {
+    ($($(#[$meta:meta])* $name:ident,)*) => {$(
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        #[serde(try_from = "String", into = "String")]
+        pub struct $name(String);
+
+        impl $name {
+            /// The number that the ID represents
+            pub fn to_u64(&self) -> u64 {
+                decode_base62(&self.0).expect("IDs are validated when they are constructed")
+            }
+
+            /// The ID that represents `number`, padded with leading zeros to [`ID_LENGTH`] characters,
+            /// or [`Error::InvalidID`] if it needs more characters than that
+            pub fn from_u64(number: u64) -> Result<Self> {
+                Self::try_from(format!("{:0>ID_LENGTH$}", encode_base62(number)))
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = Error;
+            fn try_from(value: String) -> Result<Self> {
+                match decode_base62(&value) {
+                    Some(_) if value.len() == ID_LENGTH => Ok(Self(value)),
+                    _ => Err(Error::InvalidID(value)),
+                }
+            }
+        }
+
+        string_newtype!($name);
+    )*};
+}
+
+ids! {
+    /// The ID of a project
+    ProjectId,
+    /// The ID of a version
+    VersionId,
+    /// The ID of a user
+    UserId,
+    /// The ID of a team of users that manage a project or organization
+    TeamId,
+    /// The ID of a moderation thread
+    ThreadId,
+    /// The ID of a message in a thread
+    MessageId,
+    /// The ID of an organization
+    OrganizationId,
+    /// The ID of a collection
+    CollectionId,
+    /// The ID of a report
+    ReportId,
+    /// The ID of a notification
+    NotificationId,
+    /// The ID of a payout
+    PayoutId,
+    /// The ID of a login session
+    SessionId,
+}
+
+/// Define reference types that accept either the ID of `$id` or a slug
+macro_rules! refs {
+    ($($(#[$meta:meta])* $name:ident($id:ident),)*) => {$(
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+        #[serde(try_from = "String", into = "String")]
+        pub struct $name(String);
+
+        impl TryFrom<String> for $name {
+            type Error = Error;
+            fn try_from(value: String) -> Result<Self> {
+                if decode_base62(&value).is_none() {
+                    check_id_slug(&[&value])?;
+                }
+                Ok(Self(value))
+            }
+        }
+
+        impl From<$id> for $name {
+            fn from(id: $id) -> Self {
+                Self(id.0)
+            }
+        }
+
+        impl From<&$id> for $name {
+            fn from(id: &$id) -> Self {
+                Self(id.0.clone())
+            }
+        }
+
+        string_newtype!($name);
+    )*};
+}
+
+refs! {
+    /// A project's ID or slug
+    ProjectRef(ProjectId),
+    /// A user's ID or username
+    UserRef(UserId),
+    /// An organization's ID or slug
+    OrganizationRef(OrganizationId),
+}
+
+/**
+A value that can be converted into the ID or reference `I`, validating it if needed
+
+This is implemented for strings, and for the IDs and references that convert into `I`,
+so API calls can be given whichever is at hand.
+*/
+pub trait IntoId<I> {
+    fn into_id(self) -> Result<I>;
+}
+
+impl<I, T> IntoId<I> for T
+where
+    T: TryInto<I>,
+    Error: From<T::Error>,
+{
+    fn into_id(self) -> Result<I> {
+        Ok(self.try_into()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base62() {
+        assert_eq!(decode_base62("0"), Some(0));
+        assert_eq!(decode_base62("10"), Some(62));
+        assert_eq!(decode_base62("z"), Some(61));
+        assert_eq!(encode_base62(0), "0");
+        assert_eq!(encode_base62(62), "10");
+        assert_eq!(encode_base62(u64::MAX), "LygHa16AHYF");
+        assert_eq!(decode_base62("LygHa16AHYF"), Some(u64::MAX));
+        assert_eq!(decode_base62("LygHa16AHYG"), None);
+        assert_eq!(decode_base62(""), None);
+        assert_eq!(decode_base62("sodium-fabric"), None);
+    }
+
+    #[test]
+    fn ids() {
+        let id = ProjectId::try_from("AANobbMI").unwrap();
+        assert_eq!(id, "AANobbMI");
+        assert_eq!(id.to_string(), "AANobbMI");
+        assert_eq!(ProjectId::from_u64(id.to_u64()).unwrap(), id);
+        assert!(matches!(
+            VersionId::try_from("mc1.20.1-0.5.3"),
+            Err(Error::InvalidID(_))
+        ));
+        assert!(matches!(
+            ProjectId::try_from("sodium"),
+            Err(Error::InvalidID(_))
+        ));
+
+        let padded = ProjectId::from_u64(62).unwrap();
+        assert_eq!(padded, "00000010");
+        assert_eq!(padded.to_u64(), 62);
+        assert_eq!(ProjectId::from_u64(padded.to_u64()).unwrap(), padded);
+        assert!(matches!(
+            ProjectId::from_u64(u64::MAX),
+            Err(Error::InvalidID(_))
+        ));
+
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(json, r#""AANobbMI""#);
+        assert_eq!(serde_json::from_str::<ProjectId>(&json).unwrap(), id);
+        assert!(serde_json::from_str::<ProjectId>(r#""a b""#).is_err());
+    }
+
+    #[test]
+    fn refs() {
+        assert_eq!(ProjectRef::try_from("sodium").unwrap(), "sodium");
+        assert_eq!(
+            ProjectRef::from(ProjectId::from_u64(1).unwrap()),
+            ProjectRef::try_from("00000001").unwrap()
+        );
+        assert!(matches!(
+            ProjectRef::try_from("not a slug"),
+            Err(Error::InvalidIDorSlug)
+        ));
+    }
+}
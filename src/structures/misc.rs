@@ -4,29 +4,27 @@ use super::*;
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Report {
-    pub id: ID,
+    pub id: ReportId,
     pub report_type: String,
-    /// The ID of the item being reported
-    pub item_id: ID,
-    /// The type of item being reported
-    pub item_type: ReportItemType,
+    /// The item being reported
+    #[serde(flatten)]
+    pub item: ReportItem,
     /// An extended explanation of the report
     pub body: String,
     /// The ID of the user who submitted the report
-    pub reporter: ID,
+    pub reporter: UserId,
     pub created: UtcTime,
     pub closed: bool,
     // The ID of the moderation thread associated with this report
-    pub thread_id: ID,
+    pub thread_id: ThreadId,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct ReportSubmission {
     pub report_type: String,
-    /// The ID of the item being reported
-    pub item_id: ID,
-    /// The type of item being reported
-    pub item_type: ReportItemType,
+    /// The item being reported
+    #[serde(flatten)]
+    pub item: ReportItem,
     /// An extended explanation of the report
     pub body: String,
 }
@@ -39,6 +37,67 @@ pub enum ReportItemType {
     Unknown,
 }
 
+/// The item a report is about, along with its ID
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "item_type", content = "item_id")]
+pub enum ReportItem {
+    /// The project of the given ID
+    Project(ProjectId),
+    /// The user of the given ID
+    User(UserId),
+    /// The version of the given ID
+    Version(VersionId),
+    /// An item of an unknown type, with the given ID
+    Unknown(String),
+}
+
+impl ReportItem {
+    /// The type of the item
+    pub fn item_type(&self) -> ReportItemType {
+        match self {
+            ReportItem::Project(_) => ReportItemType::Project,
+            ReportItem::User(_) => ReportItemType::User,
+            ReportItem::Version(_) => ReportItemType::Version,
+            ReportItem::Unknown(_) => ReportItemType::Unknown,
+        }
+    }
+
+    /// The ID of the item
+    pub fn id(&self) -> &str {
+        match self {
+            ReportItem::Project(id) => id.as_str(),
+            ReportItem::User(id) => id.as_str(),
+            ReportItem::Version(id) => id.as_str(),
+            ReportItem::Unknown(id) => id,
+        }
+    }
+}
+
+/// What an uploaded image will be embedded in, along with the ID of that item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImageContext {
+    /// The body of the project of the given ID
+    Project(ProjectId),
+    /// The changelog of the version of the given ID
+    Version(VersionId),
+    /// The thread message of the given ID
+    ThreadMessage(MessageId),
+    /// The report of the given ID
+    Report(ReportId),
+}
+
+impl ImageContext {
+    /// The name of the context, and the query parameter its ID is sent as
+    pub(crate) fn query(&self) -> (&'static str, &'static str, &str) {
+        match self {
+            ImageContext::Project(id) => ("project", "project_id", id.as_str()),
+            ImageContext::Version(id) => ("version", "version_id", id.as_str()),
+            ImageContext::ThreadMessage(id) => ("thread_message", "thread_message_id", id.as_str()),
+            ImageContext::Report(id) => ("report", "report_id", id.as_str()),
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone, Copy)]
 pub struct Statistics {
     /// The number of project on Modrinth
@@ -58,3 +117,28 @@ pub struct Welcome {
     pub name: String,
     pub version: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_item() {
+        let json = r#"{"report_type":"spam","item_id":"AANobbMI","item_type":"Project","body":""}"#;
+        let report: ReportSubmission = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            report.item,
+            ReportItem::Project("AANobbMI".parse().unwrap())
+        );
+        assert_eq!(report.item.item_type(), ReportItemType::Project);
+        assert_eq!(report.item.id(), "AANobbMI");
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&serde_json::to_string(&report).unwrap())
+                .unwrap(),
+            serde_json::from_str::<serde_json::Value>(json).unwrap(),
+        );
+
+        let json = r#"{"report_type":"spam","item_id":"not an id","item_type":"User","body":""}"#;
+        assert!(serde_json::from_str::<ReportSubmission>(json).is_err());
+    }
+}
@@ -1,6 +1,9 @@
 //! Models specified in the Modrinth documentation
 
+pub mod analytics;
+pub mod id;
 pub mod misc;
+pub mod payout;
 pub mod project;
 pub mod search;
 pub mod tag;
@@ -10,13 +13,16 @@ pub mod version;
 /// ISO 8601 UTC datetime
 pub type UtcTime = chrono::DateTime<chrono::Utc>;
 pub type Int = usize;
-/// A base 62 number stored as a string
+/// A base 62 number stored as a string, for IDs that do not have a [typed ID](id)
 pub type ID = String;
+/// An exact decimal number, used for monetary amounts
+pub type Decimal = rust_decimal::Decimal;
 
+pub use id::*;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
-fn deserialise_optional_url<'de, D: serde::Deserializer<'de>>(
+pub(crate) fn deserialise_optional_url<'de, D: serde::Deserializer<'de>>(
     de: D,
 ) -> Result<Option<Url>, D::Error> {
     use serde::de::{Error, Unexpected};
@@ -0,0 +1,97 @@
+//! Models related to payouts
+
+use super::*;
+
+/// A withdrawal of the user's balance
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Payout {
+    pub id: PayoutId,
+    /// The ID of the user who requested the payout
+    pub user_id: UserId,
+    pub status: PayoutStatus,
+    pub created: UtcTime,
+    /// The amount withdrawn, in USD
+    pub amount: Decimal,
+    /// The fee charged by the payout method, in USD
+    pub fee: Option<Decimal>,
+    pub method: Option<PayoutMethodType>,
+    /// The address the payout was sent to, such as an email address
+    pub method_address: Option<String>,
+    /// The ID of the payout on the payout platform
+    pub platform_id: Option<String>,
+}
+
+/// The balance of the current user, in USD
+#[derive(Deserialize, Serialize, Debug, Clone, Copy)]
+pub struct UserBalance {
+    /// The amount that can be withdrawn now
+    pub available: Decimal,
+    /// The amount that is still being processed and cannot be withdrawn yet
+    pub pending: Decimal,
+}
+
+/// A way to receive payouts
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct PayoutMethod {
+    /// The ID to use when requesting a [`Withdrawal`] with this method
+    pub id: String,
+    #[serde(rename = "type")]
+    pub method_type: PayoutMethodType,
+    pub name: String,
+    /// The ISO 3166-1 alpha-2 codes of the countries this method is available in
+    pub supported_countries: Vec<String>,
+    #[serde(deserialize_with = "deserialise_optional_url")]
+    pub image_url: Option<Url>,
+    /// The amounts that can be withdrawn using this method
+    pub interval: PayoutInterval,
+    pub fee: PayoutMethodFee,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum PayoutInterval {
+    /// Any amount between `min` and `max` can be withdrawn
+    Standard { min: Decimal, max: Decimal },
+    /// Only the listed amounts can be withdrawn, e.g. gift card denominations
+    Fixed { values: Vec<Decimal> },
+}
+
+/// The fee charged by a payout method, which is `percentage` of the amount clamped between `min` and `max`
+#[derive(Deserialize, Serialize, Debug, Clone, Copy)]
+pub struct PayoutMethodFee {
+    pub percentage: Decimal,
+    pub min: Decimal,
+    pub max: Option<Decimal>,
+}
+
+/// A request to withdraw some of the current user's balance
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Withdrawal {
+    /// The amount to withdraw, in USD
+    pub amount: Decimal,
+    pub method: PayoutMethodType,
+    /// The [`PayoutMethod::id`] of the method to use
+    pub method_id: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum PayoutStatus {
+    Success,
+    InTransit,
+    Cancelling,
+    Cancelled,
+    Failed,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PayoutMethodType {
+    PayPal,
+    Venmo,
+    Tremendous,
+    #[serde(other)]
+    Unknown,
+}
@@ -42,11 +42,11 @@ pub struct Project {
     /// The RGB color of the project, automatically generated from the project icon
     pub color: Option<Int>,
     /// The ID of the moderation thread associated with this project
-    pub thread_id: ID,
+    pub thread_id: ThreadId,
     pub monetization_status: MonetizationStatus,
-    pub id: ID,
+    pub id: ProjectId,
     /// The ID of the team that has ownership of this project
-    pub team: ID,
+    pub team: TeamId,
     pub published: UtcTime,
     pub updated: UtcTime,
     /// The date the project's status was approved
@@ -56,7 +56,7 @@ pub struct Project {
     pub license: License,
     /// A list of the version IDs of the project.
     /// This will only ever be empty if the project is a draft.
-    pub versions: Vec<ID>,
+    pub versions: Vec<VersionId>,
     /// A list of all of the game versions supported by the project
     pub game_versions: Vec<String>,
     /// A list of all of the loaders supported by the project
@@ -64,7 +64,7 @@ pub struct Project {
     /// A list of images that have been uploaded to the project's gallery
     pub gallery: Vec<GalleryItem>,
     // The ID of the organisation that owns this project
-    pub organization: Option<ID>,
+    pub organization: Option<OrganizationId>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -237,3 +237,10 @@ impl std::fmt::Display for ImageFileExt {
         write!(f, "{}", format!("{:?}", self).to_lowercase())
     }
 }
+
+impl ImageFileExt {
+    /// The MIME type of images with this extension, to be sent as the `Content-Type` of uploads
+    pub fn content_type(&self) -> String {
+        format!("image/{}", self)
+    }
+}
@@ -44,26 +44,478 @@ pub enum Facet {
     },
 }
 
+/// Formats the facet as `<type><operation><value>`, e.g. `categories:fabric` or `downloads>=1000`
+impl std::fmt::Display for Facet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Facet::ProjectType(project_type) => match serde_json::to_value(project_type) {
+                Ok(serde_json::Value::String(project_type)) => {
+                    write!(f, "project_type:{project_type}")
+                }
+                _ => Err(std::fmt::Error),
+            },
+            Facet::Categories(category) => write!(f, "categories:{category}"),
+            Facet::Versions(version) => write!(f, "versions:{version}"),
+            Facet::OpenSource(bool) => write!(f, "open_source:{bool}"),
+            Facet::License(license_id) => write!(f, "license:{license_id}"),
+            Facet::Custom {
+                _type,
+                operation,
+                value,
+            } => write!(f, "{_type}{operation}{value}"),
+        }
+    }
+}
+
+/**
+Parses a facet of the form `<type><operation><value>`
+
+Equality facets of the known types are parsed into their own variants, and all other facets into [`Facet::Custom`].
+Values surrounded by single quotes, as found in some Modrinth website URLs, have the quotes removed.
+
+## Example
+```rust
+# use ferinth::structures::{project::ProjectType, search::Facet};
+assert_eq!("project_type:mod".parse::<Facet>()?, Facet::ProjectType(ProjectType::Mod));
+assert_eq!("categories='fabric'".parse::<Facet>()?, Facet::Categories("fabric".into()));
+assert_eq!(
+    "downloads>=1000".parse::<Facet>()?,
+    Facet::Custom {
+        _type: "downloads".into(),
+        operation: ">=".into(),
+        value: "1000".into(),
+    }
+);
+# Ok::<_, ferinth::Error>(())
+```
+*/
+impl std::str::FromStr for Facet {
+    type Err = crate::Error;
+
+    fn from_str(facet: &str) -> crate::Result<Self> {
+        let invalid = || crate::Error::InvalidFacet(facet.to_owned());
+        let start = facet.find([':', '=', '!', '>', '<']).ok_or_else(invalid)?;
+        let (_type, rest) = facet.split_at(start);
+        let operation = ["!=", ">=", "<=", ":", "=", ">", "<"]
+            .into_iter()
+            .find(|operation| rest.starts_with(operation))
+            .ok_or_else(invalid)?;
+        let value = &rest[operation.len()..];
+        let value = value
+            .strip_prefix('\'')
+            .and_then(|value| value.strip_suffix('\''))
+            .unwrap_or(value);
+        if _type.is_empty()
+            || !_type.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            || value.is_empty()
+        {
+            return Err(invalid());
+        }
+
+        let equals = operation == ":" || operation == "=";
+        Ok(match _type {
+            "project_type" if equals => {
+                match serde_json::from_value(serde_json::Value::String(value.to_owned())) {
+                    Ok(project::ProjectType::Other) | Err(_) => None,
+                    Ok(project_type) => Some(Facet::ProjectType(project_type)),
+                }
+            }
+            "categories" if equals => Some(Facet::Categories(value.to_owned())),
+            "versions" if equals => Some(Facet::Versions(value.to_owned())),
+            "open_source" if equals => value.parse().ok().map(Facet::OpenSource),
+            "license" if equals => Some(Facet::License(value.to_owned())),
+            _ => None,
+        }
+        .unwrap_or_else(|| Facet::Custom {
+            _type: _type.to_owned(),
+            operation: operation.to_owned(),
+            value: value.to_owned(),
+        }))
+    }
+}
+
 impl Serialize for Facet {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        let output = match self {
-            Facet::ProjectType(project_type) => {
-                format!("project_type:{project_type:?}",)
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Facet {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        std::borrow::Cow::<str>::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/**
+Parse facets in the JSON form used by the API's `facets` parameter, e.g. `[["categories:fabric"],["versions:1.20.1"]]`
+
+The JSON can be percent-encoded, as when it is copied out of a URL.
+*/
+pub fn parse_facets(facets: &str) -> crate::Result<Vec<Vec<Facet>>> {
+    match serde_json::from_str(facets) {
+        Ok(facets) => Ok(facets),
+        Err(_) => {
+            let decoded = url::form_urlencoded::parse(format!("facets={facets}").as_bytes())
+                .next()
+                .map(|(_, facets)| facets.into_owned())
+                .unwrap_or_default();
+            Ok(serde_json::from_str(&decoded)?)
+        }
+    }
+}
+
+/**
+Get the facets of a search from the URL of a Modrinth API search, or of a search on the Modrinth website
+
+From API URLs, the `facets` parameter is used.
+From website URLs like `https://modrinth.com/mods?g=categories:fabric&v=1.20.1&e=client&l=true`,
+every `f` facet is required, the `g` facets are ORed together,
+and the game versions `v`, environments `e`, and open source `l` filters are converted to facets.
+
+## Example
+```rust
+# use ferinth::structures::search::{facets_from_url, Facet};
+let url = "https://modrinth.com/mods?q=map&g=categories:fabric&g=categories:quilt&v=1.20.1".parse()?;
+assert_eq!(
+    facets_from_url(&url)?,
+    [
+        vec![Facet::Categories("fabric".into()), Facet::Categories("quilt".into())],
+        vec![Facet::Versions("1.20.1".into())],
+    ]
+);
+# Ok::<_, Box<dyn std::error::Error>>(())
+```
+*/
+pub fn facets_from_url(url: &Url) -> crate::Result<Vec<Vec<Facet>>> {
+    let mut facets = vec![];
+    let (mut any, mut versions, mut environments) = (vec![], vec![], vec![]);
+    for (name, value) in url.query_pairs() {
+        match &*name {
+            "facets" => facets.extend(parse_facets(&value)?),
+            "f" => facets.push(vec![value.parse()?]),
+            "g" => any.push(value.parse()?),
+            "v" => versions.push(Facet::Versions(value.into_owned())),
+            "e" => {
+                let side = match &*value {
+                    "client" => "client_side",
+                    "server" => "server_side",
+                    _ => return Err(crate::Error::InvalidFacet(value.into_owned())),
+                };
+                environments.push(
+                    ["required", "optional"]
+                        .map(|support| Facet::Custom {
+                            _type: side.to_owned(),
+                            operation: ":".to_owned(),
+                            value: support.to_owned(),
+                        })
+                        .to_vec(),
+                );
             }
-            Facet::Categories(category) => format!("categories: {category}"),
-            Facet::Versions(version) => format!("versions: {version}"),
-            Facet::OpenSource(bool) => format!("open_source: {bool}"),
-            Facet::License(license_id) => format!("license: {license_id}"),
-            Facet::Custom {
-                _type,
-                operation,
-                value,
-            } => format!("{_type} {operation} {value}"),
+            "l" if value == "true" => facets.push(vec![Facet::OpenSource(true)]),
+            _ => {}
+        }
+    }
+    for group in [any, versions] {
+        if !group.is_empty() {
+            facets.push(group);
+        }
+    }
+    facets.extend(environments);
+    Ok(facets)
+}
+
+/// A comparison used in a custom facet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    /// `:`
+    Equals,
+    /// `!=`
+    NotEquals,
+    /// `>`
+    GreaterThan,
+    /// `>=`
+    GreaterOrEqual,
+    /// `<`
+    LessThan,
+    /// `<=`
+    LessOrEqual,
+}
+
+impl Operator {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Operator::Equals => ":",
+            Operator::NotEquals => "!=",
+            Operator::GreaterThan => ">",
+            Operator::GreaterOrEqual => ">=",
+            Operator::LessThan => "<",
+            Operator::LessOrEqual => "<=",
+        }
+    }
+}
+
+impl std::fmt::Display for Operator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A field of projects that search results can be filtered by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Downloads,
+    Follows,
+    DateCreated,
+    DateModified,
+    ClientSide,
+    ServerSide,
+    ProjectId,
+    /// The username of the project's author
+    Author,
+    Title,
+    /// Mod loaders and categories
+    Categories,
+}
+
+impl Field {
+    /// The name of the field in facets
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Field::Downloads => "downloads",
+            Field::Follows => "follows",
+            Field::DateCreated => "date_created",
+            Field::DateModified => "date_modified",
+            Field::ClientSide => "client_side",
+            Field::ServerSide => "server_side",
+            Field::ProjectId => "project_id",
+            Field::Author => "author",
+            Field::Title => "title",
+            Field::Categories => "categories",
+        }
+    }
+
+    /// Match projects whose field compares to `value` using `operator`
+    pub fn compare(self, operator: Operator, value: impl ToString) -> Filter {
+        Filter::Facet(Facet::Custom {
+            _type: self.as_str().to_owned(),
+            operation: operator.as_str().to_owned(),
+            value: value.to_string(),
+        })
+    }
+
+    /// Match projects whose field is `value`
+    pub fn equals(self, value: impl ToString) -> Filter {
+        self.compare(Operator::Equals, value)
+    }
+
+    /// Match projects whose field is not `value`
+    pub fn not_equals(self, value: impl ToString) -> Filter {
+        self.compare(Operator::NotEquals, value)
+    }
+
+    /// Match projects whose field is greater than `value`
+    pub fn gt(self, value: impl ToString) -> Filter {
+        self.compare(Operator::GreaterThan, value)
+    }
+
+    /// Match projects whose field is greater than or equal to `value`
+    pub fn ge(self, value: impl ToString) -> Filter {
+        self.compare(Operator::GreaterOrEqual, value)
+    }
+
+    /// Match projects whose field is less than `value`
+    pub fn lt(self, value: impl ToString) -> Filter {
+        self.compare(Operator::LessThan, value)
+    }
+
+    /// Match projects whose field is less than or equal to `value`
+    pub fn le(self, value: impl ToString) -> Filter {
+        self.compare(Operator::LessOrEqual, value)
+    }
+
+    /**
+    Match projects whose date field compares to `date` using `operator`
+
+    [`Self::DateCreated`] and [`Self::DateModified`] are compared using their UNIX timestamps,
+    as dates cannot be compared directly.
+    */
+    pub fn compare_date(self, operator: Operator, date: UtcTime) -> Filter {
+        let field = match self {
+            Field::DateCreated => "created_timestamp",
+            Field::DateModified => "modified_timestamp",
+            field => field.as_str(),
         };
-        serializer.collect_str(&output)
+        Filter::Facet(Facet::Custom {
+            _type: field.to_owned(),
+            operation: operator.as_str().to_owned(),
+            value: date.timestamp().to_string(),
+        })
+    }
+}
+
+/// A boolean combination of facets
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Filter {
+    Facet(Facet),
+    /// Matches projects that match all the filters
+    All(Vec<Filter>),
+    /// Matches projects that match any of the filters
+    Any(Vec<Filter>),
+}
+
+impl From<Facet> for Filter {
+    fn from(facet: Facet) -> Self {
+        Filter::Facet(facet)
+    }
+}
+
+impl Filter {
+    /// Match projects that match both `self` and `other`
+    pub fn and(self, other: impl Into<Filter>) -> Self {
+        match self {
+            Filter::All(mut filters) => {
+                filters.push(other.into());
+                Filter::All(filters)
+            }
+            filter => Filter::All(vec![filter, other.into()]),
+        }
+    }
+
+    /// Match projects that match either `self` or `other`
+    pub fn or(self, other: impl Into<Filter>) -> Self {
+        match self {
+            Filter::Any(mut filters) => {
+                filters.push(other.into());
+                Filter::Any(filters)
+            }
+            filter => Filter::Any(vec![filter, other.into()]),
+        }
+    }
+
+    /**
+    Convert the filter to the facets that the API accepts,
+    where the inner vectors are ORed together and the outer vector is ANDed together
+
+    ORs of ANDs are expanded, so deeply nested filters can produce many facets.
+    */
+    pub fn to_facets(&self) -> Vec<Vec<Facet>> {
+        match self {
+            Filter::Facet(facet) => vec![vec![facet.clone()]],
+            Filter::All(filters) => filters.iter().flat_map(Filter::to_facets).collect(),
+            Filter::Any(filters) => filters.iter().fold(vec![vec![]], |clauses, filter| {
+                let other = filter.to_facets();
+                clauses
+                    .iter()
+                    .flat_map(|clause| {
+                        other.iter().map(move |other| {
+                            let mut clause = clause.clone();
+                            clause.extend(other.iter().cloned());
+                            clause
+                        })
+                    })
+                    .collect()
+            }),
+        }
+    }
+}
+
+/**
+A search for projects, which turns into the URL parameters of the search API call
+
+## Example
+```rust
+# use ferinth::structures::{project::ProjectType, search::{Facet, Field, SearchQuery, Sort}};
+let query = SearchQuery::new("map")
+    .sort(Sort::Downloads)
+    .filter(Facet::ProjectType(ProjectType::Mod))
+    .filter(Field::Categories.equals("fabric").or(Field::Categories.equals("quilt")))
+    .filter(Field::Downloads.ge(10_000));
+assert_eq!(query.facets().len(), 3);
+```
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchQuery {
+    pub query: String,
+    pub sort: Sort,
+    pub filter: Option<Filter>,
+    /// The maximum number of hits to return, from 0 to 100
+    pub limit: Option<Int>,
+    /// The number of hits to skip
+    pub offset: Option<Int>,
+}
+
+impl SearchQuery {
+    /// Search for `query`, sorted by relevance
+    pub fn new(query: impl Into<String>) -> Self {
+        Self {
+            query: query.into(),
+            sort: Sort::Relevance,
+            filter: None,
+            limit: None,
+            offset: None,
+        }
+    }
+
+    pub fn sort(mut self, sort: Sort) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    /// Only return projects that match `filter`, in addition to any previous filters
+    pub fn filter(mut self, filter: impl Into<Filter>) -> Self {
+        self.filter = Some(match self.filter {
+            Some(previous) => previous.and(filter),
+            None => filter.into(),
+        });
+        self
+    }
+
+    pub fn limit(mut self, limit: Int) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn offset(mut self, offset: Int) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// The facets of the filter, where the inner vectors are ORed together and the outer vector is ANDed together
+    pub fn facets(&self) -> Vec<Vec<Facet>> {
+        let mut facets = self
+            .filter
+            .as_ref()
+            .map(Filter::to_facets)
+            .unwrap_or_default();
+        facets.retain(|facets| !facets.is_empty());
+        facets
+    }
+
+    /// The URL query parameters of the search
+    pub fn query_pairs(&self) -> serde_json::Result<Vec<(&'static str, String)>> {
+        let mut pairs = vec![
+            ("query", self.query.clone()),
+            ("index", self.sort.to_string()),
+        ];
+        if let Some(limit) = self.limit {
+            pairs.push(("limit", limit.to_string()));
+        }
+        if let Some(offset) = self.offset {
+            pairs.push(("offset", offset.to_string()));
+        }
+        let facets = self.facets();
+        if !facets.is_empty() {
+            pairs.push(("facets", serde_json::to_string(&facets)?));
+        }
+        Ok(pairs)
     }
 }
 
@@ -95,9 +547,9 @@ pub struct SearchHit {
     /// The RGB color of the project, automatically generated from the project icon
     pub color: Option<Int>,
     /// The ID of the moderation thread associated with this project
-    pub thread_id: Option<ID>,
+    pub thread_id: Option<ThreadId>,
     pub monetization_status: Option<project::MonetizationStatus>,
-    pub project_id: ID,
+    pub project_id: ProjectId,
     /// Username of the project's authour
     pub author: String,
     /// A list of the project's primary/featured categories
@@ -115,3 +567,212 @@ pub struct SearchHit {
     pub gallery: Vec<Url>,
     pub featured_gallery: Option<Url>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn custom(field: &str, operation: &str, value: &str) -> Facet {
+        Facet::Custom {
+            _type: field.to_owned(),
+            operation: operation.to_owned(),
+            value: value.to_owned(),
+        }
+    }
+
+    #[test]
+    fn typed_facets() {
+        assert_eq!(
+            Field::Downloads.gt(100),
+            Filter::Facet(custom("downloads", ">", "100"))
+        );
+        assert_eq!(
+            Field::ServerSide.not_equals("unsupported"),
+            Filter::Facet(custom("server_side", "!=", "unsupported"))
+        );
+        assert_eq!(
+            Field::DateModified.compare_date(Operator::LessOrEqual, chrono::DateTime::UNIX_EPOCH),
+            Filter::Facet(custom("modified_timestamp", "<=", "0"))
+        );
+    }
+
+    #[test]
+    fn and_of_ors() {
+        let filter = Field::Categories
+            .equals("fabric")
+            .or(Field::Categories.equals("quilt"))
+            .and(Field::Follows.ge(10))
+            .and(Field::Author.equals("jellysquid3"));
+        assert_eq!(
+            filter.to_facets(),
+            [
+                vec![
+                    custom("categories", ":", "fabric"),
+                    custom("categories", ":", "quilt")
+                ],
+                vec![custom("follows", ">=", "10")],
+                vec![custom("author", ":", "jellysquid3")],
+            ]
+        );
+    }
+
+    #[test]
+    fn or_of_ands_is_expanded() {
+        // (a AND b) OR c == (a OR c) AND (b OR c)
+        let (a, b, c) = (
+            Field::Title.equals("a"),
+            Field::Title.equals("b"),
+            Field::Title.equals("c"),
+        );
+        assert_eq!(
+            a.and(b).or(c).to_facets(),
+            [
+                vec![custom("title", ":", "a"), custom("title", ":", "c")],
+                vec![custom("title", ":", "b"), custom("title", ":", "c")],
+            ]
+        );
+    }
+
+    #[test]
+    fn query_pairs() {
+        let query = SearchQuery::new("sodium")
+            .sort(Sort::Updated)
+            .limit(20)
+            .offset(40)
+            .filter(Facet::Versions("1.20.1".into()));
+        let pairs = query.query_pairs().unwrap();
+        assert_eq!(
+            pairs[..4],
+            [
+                ("query", "sodium".to_owned()),
+                ("index", "updated".to_owned()),
+                ("limit", "20".to_owned()),
+                ("offset", "40".to_owned()),
+            ]
+        );
+        assert_eq!(pairs[4], ("facets", r#"[["versions:1.20.1"]]"#.to_owned()));
+
+        assert_eq!(SearchQuery::new("").query_pairs().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn facet_strings() {
+        for (facet, string) in [
+            (
+                Facet::ProjectType(project::ProjectType::Resourcepack),
+                "project_type:resourcepack",
+            ),
+            (
+                Facet::ProjectType(project::ProjectType::MinecraftJavaServer),
+                "project_type:minecraft_java_server",
+            ),
+            (Facet::Categories("fabric".into()), "categories:fabric"),
+            (Facet::Versions("1.20.1".into()), "versions:1.20.1"),
+            (Facet::OpenSource(true), "open_source:true"),
+            (Facet::License("MIT".into()), "license:MIT"),
+            (custom("downloads", ">", "100"), "downloads>100"),
+            (custom("follows", ">=", "10"), "follows>=10"),
+            (custom("downloads", "<", "100"), "downloads<100"),
+            (custom("follows", "<=", "10"), "follows<=10"),
+            (
+                custom("server_side", "!=", "unsupported"),
+                "server_side!=unsupported",
+            ),
+            (custom("author", ":", "jellysquid3"), "author:jellysquid3"),
+            (custom("title", "=", "Sodium"), "title=Sodium"),
+        ] {
+            assert_eq!(facet.to_string(), string);
+            assert_eq!(string.parse::<Facet>().unwrap(), facet);
+            assert_eq!(
+                serde_json::to_value(&facet).unwrap(),
+                serde_json::Value::String(string.to_owned())
+            );
+        }
+    }
+
+    #[test]
+    fn parse_equivalent_forms() {
+        assert_eq!(
+            "categories='fabric'".parse::<Facet>().unwrap(),
+            Facet::Categories("fabric".into())
+        );
+        assert_eq!(
+            "project_type=mod".parse::<Facet>().unwrap(),
+            Facet::ProjectType(project::ProjectType::Mod)
+        );
+        assert_eq!(
+            "open_source:maybe".parse::<Facet>().unwrap(),
+            custom("open_source", ":", "maybe")
+        );
+        for invalid in [
+            "categories",
+            ":fabric",
+            "categories:",
+            "downloads!100",
+            "a b:c",
+        ] {
+            assert!(
+                matches!(invalid.parse::<Facet>(), Err(crate::Error::InvalidFacet(_))),
+                "{invalid:?} was accepted"
+            );
+        }
+    }
+
+    #[test]
+    fn facets_json_round_trip() {
+        let facets = vec![
+            vec![
+                Facet::Categories("fabric".into()),
+                Facet::Categories("quilt".into()),
+            ],
+            vec![custom("downloads", ">=", "1000")],
+        ];
+        let json = serde_json::to_string(&facets).unwrap();
+        assert_eq!(
+            json,
+            r#"[["categories:fabric","categories:quilt"],["downloads>=1000"]]"#
+        );
+        assert_eq!(parse_facets(&json).unwrap(), facets);
+        assert_eq!(
+            parse_facets("%5B%5B%22categories%3Afabric%22%2C%22categories%3Aquilt%22%5D%2C%5B%22downloads%3E%3D1000%22%5D%5D")
+                .unwrap(),
+            facets
+        );
+    }
+
+    #[test]
+    fn web_url() {
+        let url = Url::parse(
+            "https://modrinth.com/mods?q=map&f=project_type:mod&g=categories:fabric&g=categories:quilt&v=1.20.1&v=1.20.2&e=server&l=true",
+        )
+        .unwrap();
+        assert_eq!(
+            facets_from_url(&url).unwrap(),
+            [
+                vec![Facet::ProjectType(project::ProjectType::Mod)],
+                vec![Facet::OpenSource(true)],
+                vec![
+                    Facet::Categories("fabric".into()),
+                    Facet::Categories("quilt".into())
+                ],
+                vec![
+                    Facet::Versions("1.20.1".into()),
+                    Facet::Versions("1.20.2".into())
+                ],
+                vec![
+                    custom("server_side", ":", "required"),
+                    custom("server_side", ":", "optional")
+                ],
+            ]
+        );
+
+        let url = Url::parse(
+            "https://api.modrinth.com/v2/search?query=map&facets=%5B%5B%22versions%3A1.20.1%22%5D%5D",
+        )
+        .unwrap();
+        assert_eq!(
+            facets_from_url(&url).unwrap(),
+            [vec![Facet::Versions("1.20.1".into())]]
+        );
+    }
+}
@@ -20,7 +20,7 @@ pub struct User {
     /// Various data relating to the user's payouts status,
     /// only visible to the user itself when authenticated
     pub payout_data: Option<PayoutData>,
-    pub id: ID,
+    pub id: UserId,
     pub avatar_url: Option<Url>,
     pub created: UtcTime,
     pub role: UserRole,
@@ -32,16 +32,40 @@ pub struct User {
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct PayoutData {
-    pub balance: f64,
+    pub balance: Decimal,
     pub paypal_address: Option<String>,
     pub paypal_country: Option<String>,
     pub venmo_handle: Option<String>,
 }
 
+/// A login session of the current user
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Session {
+    pub id: SessionId,
+    /// The session token, only visible right after the session has been created or refreshed
+    pub session: Option<String>,
+    pub user_id: UserId,
+    pub created: UtcTime,
+    pub last_login: UtcTime,
+    /// When the session expires if it is not refreshed
+    pub expires: UtcTime,
+    /// When the session can no longer be refreshed
+    pub refresh_expires: UtcTime,
+    pub os: Option<String>,
+    pub platform: Option<String>,
+    pub user_agent: String,
+    pub city: Option<String>,
+    pub country: Option<String>,
+    /// The IP address the session was last used from
+    pub ip: String,
+    /// Whether this is the session making the request
+    pub current: bool,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct TeamMember {
     /// The ID of the member's team
-    pub team_id: ID,
+    pub team_id: TeamId,
     pub user: User,
     pub role: String,
     /// The user's permissions in bitflag format
@@ -60,9 +84,9 @@ pub struct TeamMember {
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Notification {
-    pub id: ID,
+    pub id: NotificationId,
     /// The ID of the user who received the notification
-    pub user_id: ID,
+    pub user_id: UserId,
     pub title: String,
     pub text: String,
     /// A _relative_ link to the related project/version
@@ -87,32 +111,32 @@ pub struct NotificationAction {
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum NotificationBody {
     ProjectUpdate {
-        project_id: ID,
-        version_id: ID,
+        project_id: ProjectId,
+        version_id: VersionId,
     },
     TeamInvite {
-        project_id: ID,
-        team_id: ID,
-        invited_by: ID,
+        project_id: ProjectId,
+        team_id: TeamId,
+        invited_by: UserId,
         role: String,
     },
     OrganizationInvite {
-        organization_id: ID,
-        invited_by: ID,
-        team_id: ID,
+        organization_id: OrganizationId,
+        invited_by: UserId,
+        team_id: TeamId,
         role: String,
     },
     StatusChange {
-        project_id: ID,
+        project_id: ProjectId,
         old_status: project::ProjectStatus,
         new_status: project::ProjectStatus,
     },
     ModeratorMessage {
-        thread_id: ID,
-        message_id: ID,
+        thread_id: ThreadId,
+        message_id: MessageId,
 
-        project_id: Option<ID>,
-        report_id: Option<ID>,
+        project_id: Option<ProjectId>,
+        report_id: Option<ReportId>,
     },
     LegacyMarkdown {
         notification_type: Option<String>,
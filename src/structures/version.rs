@@ -18,16 +18,26 @@ pub struct Version {
     pub featured: bool,
     pub status: Option<Status>,
     pub requested_status: Option<RequestedStatus>,
-    pub id: ID,
-    pub project_id: ID,
+    pub id: VersionId,
+    pub project_id: ProjectId,
     /// The user ID of the author who published this version
-    pub author_id: ID,
+    pub author_id: UserId,
     pub date_published: UtcTime,
     pub downloads: Int,
     /// A list of files available for download
     pub files: Vec<VersionFile>,
 }
 
+impl Version {
+    /// Get the primary file of this version, or the first file if none are marked as primary
+    pub fn primary_file(&self) -> Option<&VersionFile> {
+        self.files
+            .iter()
+            .find(|file| file.primary)
+            .or_else(|| self.files.first())
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct VersionFile {
     pub hashes: Hash,
@@ -53,6 +63,17 @@ pub struct Hash {
     pub others: std::collections::HashMap<String, String>,
 }
 
+impl Hash {
+    /// Get the hash computed using `algorithm`, if it was provided
+    pub fn get(&self, algorithm: HashAlgorithm) -> Option<&str> {
+        match algorithm {
+            HashAlgorithm::SHA512 => Some(&self.sha512),
+            HashAlgorithm::SHA1 => Some(&self.sha1),
+            HashAlgorithm::Other => None,
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct LatestVersionBody {
     pub loaders: Vec<String>,
@@ -69,8 +90,8 @@ pub struct LatestVersionsBody {
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Dependency {
-    pub version_id: Option<ID>,
-    pub project_id: Option<ID>,
+    pub version_id: Option<VersionId>,
+    pub project_id: Option<ProjectId>,
     pub file_name: Option<String>,
     pub dependency_type: DependencyType,
 }
@@ -84,6 +105,12 @@ pub enum HashAlgorithm {
     Other,
 }
 
+impl std::fmt::Display for HashAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", format!("{self:?}").to_lowercase())
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 #[serde(rename_all = "lowercase")]
 pub enum VersionType {
@@ -0,0 +1,120 @@
+//! A minimal HTTP server serving fixed files, to test downloads without the network
+
+use crate::hashing::hash_bytes;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+use url::Url;
+
+pub(crate) struct TestServer {
+    /// The URL the server is listening at
+    pub url: Url,
+    /// The number of requests the server has received
+    pub requests: Arc<AtomicUsize>,
+    /// The headers of the requests the server has received, with lowercase names
+    pub headers: Arc<Mutex<Vec<HashMap<String, String>>>>,
+}
+
+impl TestServer {
+    /// Serve the `files` at their paths until the test ends.
+    /// `Range: bytes=N-` requests are supported,
+    /// and files are sent with an `ETag` that `If-None-Match` requests are checked against.
+    pub async fn serve(files: &[(&str, &[u8])]) -> Self {
+        let files: Arc<HashMap<String, Vec<u8>>> = Arc::new(
+            files
+                .iter()
+                .map(|(path, data)| (format!("/{path}"), data.to_vec()))
+                .collect(),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("http://{}/", listener.local_addr().unwrap())).unwrap();
+        let requests = Arc::new(AtomicUsize::new(0));
+        let headers = Arc::new(Mutex::new(vec![]));
+
+        let counter = requests.clone();
+        let received = headers.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let files = files.clone();
+                let counter = counter.clone();
+                let received = received.clone();
+                tokio::spawn(async move {
+                    let mut request = vec![];
+                    let mut buf = [0; 1024];
+                    // Read until the end of the headers, ignoring any body
+                    while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                        match stream.read(&mut buf).await.unwrap() {
+                            0 => return,
+                            read => request.extend_from_slice(&buf[..read]),
+                        }
+                    }
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    let request = String::from_utf8(request).unwrap();
+                    received.lock().unwrap().push(
+                        request
+                            .lines()
+                            .skip(1)
+                            .map_while(|line| line.split_once(": "))
+                            .map(|(name, value)| (name.to_lowercase(), value.to_owned()))
+                            .collect(),
+                    );
+                    let path = request.split(' ').nth(1).unwrap();
+                    let start = request.lines().find_map(|line| {
+                        line.to_lowercase()
+                            .strip_prefix("range: bytes=")?
+                            .strip_suffix('-')?
+                            .parse::<usize>()
+                            .ok()
+                    });
+
+                    let etag = files
+                        .get(path)
+                        .map(|data| format!("\"{}\"", hash_bytes(data).sha1));
+                    let matches = request.lines().any(|line| {
+                        line.to_lowercase()
+                            .strip_prefix("if-none-match: ")
+                            .is_some_and(|value| Some(value) == etag.as_deref())
+                    });
+
+                    let (head, body) = match (files.get(path), start) {
+                        (None, _) => ("404 Not Found".to_owned(), &[][..]),
+                        (Some(_), None) if matches => ("304 Not Modified".to_owned(), &[][..]),
+                        (Some(data), None) => (
+                            format!("200 OK\r\nETag: {}", etag.as_deref().unwrap()),
+                            &data[..],
+                        ),
+                        (Some(data), Some(start)) => (
+                            format!(
+                                "206 Partial Content\r\nContent-Range: bytes {start}-{}/{}",
+                                data.len() - 1,
+                                data.len()
+                            ),
+                            &data[start..],
+                        ),
+                    };
+                    let response = format!(
+                        "HTTP/1.1 {head}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                        body.len()
+                    );
+                    stream.write_all(response.as_bytes()).await.unwrap();
+                    stream.write_all(body).await.unwrap();
+                });
+            }
+        });
+
+        Self {
+            url,
+            requests,
+            headers,
+        }
+    }
+}
@@ -0,0 +1,119 @@
+//! Versions, and lookups of them by file hashes, for tests without the network
+
+use crate::{
+    hashing::hash_bytes,
+    structures::version::{LatestVersionBody, Version},
+    update_checker::HashLookup,
+    Result,
+};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use url::Url;
+
+/// A release of version `id` of `project_id` for Fabric 1.20.1, published on the 1st of January 2024
+pub(crate) fn version(id: &str, project_id: &str) -> TestVersion {
+    TestVersion(json!({
+        "name": id,
+        "version_number": id,
+        "changelog": null,
+        "dependencies": [],
+        "game_versions": ["1.20.1"],
+        "version_type": "release",
+        "loaders": ["fabric"],
+        "featured": false,
+        "status": "listed",
+        "requested_status": null,
+        "id": id,
+        "project_id": project_id,
+        "author_id": "XXXXXXXX",
+        "date_published": "2024-01-01T00:00:00Z",
+        "downloads": 0,
+        "files": [],
+    }))
+}
+
+pub(crate) struct TestVersion(Value);
+
+impl TestVersion {
+    /// Publish the version on `day` of January 2024
+    pub fn day(mut self, day: u8) -> Self {
+        self.0["date_published"] = json!(format!("2024-01-{day:02}T00:00:00Z"));
+        self
+    }
+
+    pub fn version_type(mut self, version_type: &str) -> Self {
+        self.0["version_type"] = json!(version_type);
+        self
+    }
+
+    pub fn loader(mut self, loader: &str) -> Self {
+        self.0["loaders"] = json!([loader]);
+        self
+    }
+
+    pub fn game_version(mut self, game_version: &str) -> Self {
+        self.0["game_versions"] = json!([game_version]);
+        self
+    }
+
+    pub fn dependencies(mut self, dependencies: Value) -> Self {
+        self.0["dependencies"] = dependencies;
+        self
+    }
+
+    /// Add a primary file named `filename` with the contents `data`, downloaded from `url`
+    pub fn file(mut self, filename: &str, data: &[u8], url: Url) -> Self {
+        let hashes = hash_bytes(data);
+        self.0["files"] = json!([{
+            "hashes": { "sha512": hashes.sha512, "sha1": hashes.sha1 },
+            "url": url,
+            "filename": filename,
+            "primary": true,
+            "size": data.len(),
+            "file_type": null,
+        }]);
+        self
+    }
+
+    pub fn build(self) -> Version {
+        serde_json::from_value(self.0).unwrap()
+    }
+}
+
+/// Knows `versions` by the hashes of their files, with the newest version of each project as the latest
+pub(crate) struct TestLookup(pub Vec<Version>);
+
+impl HashLookup for TestLookup {
+    async fn versions(&self, hashes: Vec<String>) -> Result<HashMap<String, Version>> {
+        Ok(self
+            .0
+            .iter()
+            .flat_map(|version| {
+                version
+                    .files
+                    .iter()
+                    .map(move |file| (file.hashes.sha512.clone(), version.clone()))
+            })
+            .filter(|(hash, _)| hashes.contains(hash))
+            .collect())
+    }
+
+    async fn latest_versions(
+        &self,
+        hashes: Vec<String>,
+        _: LatestVersionBody,
+    ) -> Result<HashMap<String, Version>> {
+        let current = self.versions(hashes).await?;
+        Ok(current
+            .into_iter()
+            .filter_map(|(hash, current)| {
+                let latest = self
+                    .0
+                    .iter()
+                    .filter(|version| version.project_id == current.project_id)
+                    .max_by_key(|version| version.date_published)?;
+                Some((hash, latest.clone()))
+            })
+            .collect())
+    }
+}
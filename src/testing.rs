@@ -0,0 +1,314 @@
+/*!
+Record and replay API responses, to test code built on ferinth deterministically and without the network
+
+Requests are keyed by their method, URL, and body.
+Each response is stored as a JSON fixture file in a directory, which can be committed alongside the tests that use it.
+Requests with streamed bodies cannot be keyed, so they are sent without being recorded,
+and return [`Error::MissingFixture`] when replaying.
+
+## Example
+```no_run
+# use ferinth::testing::{Fixtures, FixtureMode};
+# tokio_test::block_on(async {
+// Record missing fixtures on the first run, and replay them afterwards
+let modrinth = ferinth::Ferinth::default().with_fixtures(Fixtures::new("tests/fixtures"));
+let sodium = modrinth.project_get("sodium").await?;
+
+// Fail instead of contacting the server if a fixture is missing
+let modrinth = ferinth::Ferinth::default()
+    .with_fixtures(Fixtures::new("tests/fixtures").mode(FixtureMode::Replay));
+# Ok::<_, ferinth::Error>(()) }).unwrap()
+```
+*/
+
+use crate::{request::Transport, Authenticated, Error, Ferinth, Result};
+use reqwest::{Method, Response};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{path::PathBuf, sync::Arc};
+use tokio::fs;
+use url::Url;
+
+/// The environment variable that [`Fixtures::new`] reads the [`FixtureMode`] from
+pub const FIXTURE_MODE_VAR: &str = "FERINTH_FIXTURES";
+
+/// Whether fixtures are recorded or replayed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FixtureMode {
+    /// Replay fixtures that exist, and record those that are missing
+    #[default]
+    Auto,
+    /// Send all requests to the server, and record their responses, overwriting existing fixtures
+    Record,
+    /// Only replay fixtures, returning [`Error::MissingFixture`] for requests that have not been recorded
+    Replay,
+}
+
+impl FixtureMode {
+    /// The mode set by the value of the [`FIXTURE_MODE_VAR`] environment variable,
+    /// which is one of `auto`, `record`, or `replay`
+    pub fn from_env() -> Option<Self> {
+        match std::env::var(FIXTURE_MODE_VAR).ok()?.as_str() {
+            "auto" => Some(Self::Auto),
+            "record" => Some(Self::Record),
+            "replay" => Some(Self::Replay),
+            _ => None,
+        }
+    }
+}
+
+/// A directory of recorded request and response pairs
+#[derive(Debug, Clone)]
+pub struct Fixtures {
+    dir: PathBuf,
+    mode: FixtureMode,
+}
+
+/// A recorded request and its response
+#[derive(Deserialize, Serialize, Debug)]
+struct Fixture {
+    method: String,
+    url: Url,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    request_body: Option<String>,
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Body,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(untagged)]
+enum Body {
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+impl Fixtures {
+    /// Use the fixtures in `dir`, in the mode set by the [`FIXTURE_MODE_VAR`] environment variable,
+    /// or [`FixtureMode::Auto`] if it is not set
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            mode: FixtureMode::from_env().unwrap_or_default(),
+        }
+    }
+
+    /// Use the fixtures in `mode`, regardless of the environment
+    pub fn mode(mut self, mode: FixtureMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// The path of the fixture of a `method` request to `url` with `body`
+    fn path(&self, method: &Method, url: &Url, body: &[u8]) -> PathBuf {
+        let mut hasher = Sha256::new();
+        hasher.update(method.as_str());
+        hasher.update(url.as_str());
+        hasher.update(body);
+        let hash = format!("{:x}", hasher.finalize());
+
+        // Start with the path so that fixtures are easy to find
+        let path = url
+            .path()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .take(64)
+            .collect::<String>();
+        self.dir.join(format!(
+            "{}{}_{}.json",
+            method.as_str().to_lowercase(),
+            path,
+            &hash[..16]
+        ))
+    }
+
+    /// Replay the response to `request`, or send it and record the response, according to the mode
+    pub(crate) async fn send(&self, request: reqwest::RequestBuilder) -> Result<Response> {
+        let (client, request) = request.build_split();
+        let request = request?;
+        let method = request.method().clone();
+        let url = request.url().clone();
+        // Streamed bodies cannot be read without sending them
+        let Some(body) = request
+            .body()
+            .map_or(Some(&[][..]), reqwest::Body::as_bytes)
+            .map(<[u8]>::to_vec)
+        else {
+            return match self.mode {
+                FixtureMode::Replay => Err(Error::MissingFixture(format!(
+                    "{method} {url}, which has a streamed body"
+                ))),
+                FixtureMode::Auto | FixtureMode::Record => Ok(client.execute(request).await?),
+            };
+        };
+        let request_body = (!body.is_empty()).then(|| String::from_utf8_lossy(&body).into_owned());
+        let path = self.path(&method, &url, &body);
+
+        if self.mode != FixtureMode::Record {
+            let fixture = fs::read(&path)
+                .await
+                .ok()
+                .and_then(|json| serde_json::from_slice::<Fixture>(&json).ok())
+                // Guard against hash collisions
+                .filter(|fixture| {
+                    fixture.method == method.as_str()
+                        && fixture.url == url
+                        && fixture.request_body == request_body
+                });
+            match fixture {
+                Some(fixture) => return fixture.into_response(),
+                None if self.mode == FixtureMode::Replay => {
+                    return Err(Error::MissingFixture(format!("{method} {url}")))
+                }
+                None => {}
+            }
+        }
+
+        let response = client.execute(request).await?;
+        let headers = response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_owned())))
+            .collect();
+        let status = response.status().as_u16();
+        let bytes = response.bytes().await?.to_vec();
+        let fixture = Fixture {
+            method: method.to_string(),
+            url,
+            request_body,
+            status,
+            headers,
+            body: match String::from_utf8(bytes) {
+                Ok(text) => Body::Text(text),
+                Err(err) => Body::Binary(err.into_bytes()),
+            },
+        };
+        fs::create_dir_all(&self.dir).await?;
+        fs::write(&path, serde_json::to_vec_pretty(&fixture)?).await?;
+        fixture.into_response()
+    }
+}
+
+impl Fixture {
+    fn into_response(self) -> Result<Response> {
+        let mut response = http::Response::builder().status(self.status);
+        for (name, value) in &self.headers {
+            response = response.header(name, value);
+        }
+        let body = match self.body {
+            Body::Text(text) => text.into_bytes(),
+            Body::Binary(bytes) => bytes,
+        };
+        let response = response
+            .body(body)
+            .map_err(|err| Error::MissingFixture(format!("{} {}: {err}", self.method, self.url)))?;
+        Ok(response.into())
+    }
+}
+
+/**
+The container that the doc tests in this crate make API calls with,
+which replays the fixtures in its `fixtures` directory, or records them in the mode set by [`FIXTURE_MODE_VAR`]
+
+Replaying a request that has not been recorded fails with [`Error::MissingFixture`],
+so new doc tests need their fixtures to be recorded with `FERINTH_FIXTURES=record cargo test --doc`.
+*/
+#[doc(hidden)]
+pub fn doc_client() -> Ferinth<()> {
+    Ferinth::default().with_fixtures(doc_fixtures())
+}
+
+/// Like [`doc_client`], but sending the token in the `MODRINTH_TOKEN` environment variable,
+/// which is only needed when recording
+#[doc(hidden)]
+pub fn doc_client_authenticated() -> Ferinth<Authenticated> {
+    Ferinth::<Authenticated>::new(
+        env!("CARGO_CRATE_NAME"),
+        Some(env!("CARGO_PKG_VERSION")),
+        None,
+        std::env::var("MODRINTH_TOKEN").unwrap_or_default(),
+    )
+    .expect("MODRINTH_TOKEN is not a valid header value")
+    .with_fixtures(doc_fixtures())
+}
+
+fn doc_fixtures() -> Fixtures {
+    Fixtures::new(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures"))
+        .mode(FixtureMode::from_env().unwrap_or(FixtureMode::Replay))
+}
+
+impl<T> Ferinth<T> {
+    /// Record the responses of API calls made with this container to, or replay them from, `fixtures`
+    pub fn with_fixtures(mut self, fixtures: Fixtures) -> Self {
+        self.client.transport = Transport::Fixtures(Arc::new(fixtures));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        request::{Client, RequestBuilderCustomSend},
+        test_server::TestServer,
+    };
+    use std::sync::atomic::Ordering;
+
+    fn client(dir: &std::path::Path, mode: FixtureMode) -> Client {
+        let mut client = Client::new(reqwest::Client::new());
+        client.transport = Transport::Fixtures(Arc::new(Fixtures::new(dir).mode(mode)));
+        client
+    }
+
+    #[tokio::test]
+    async fn record_and_replay() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let server = TestServer::serve(&[("v2/tag/loader", br#"["fabric","forge"]"#)]).await;
+        let url = server.url.join("v2/tag/loader").unwrap();
+        let missing = server.url.join("v2/tag/category").unwrap();
+
+        let replay = client(dir.path(), FixtureMode::Replay);
+        assert!(matches!(
+            replay.get(url.clone()).custom_send().await,
+            Err(Error::MissingFixture(_))
+        ));
+
+        let auto = client(dir.path(), FixtureMode::Auto);
+        let loaders: Vec<String> = auto.get(url.clone()).custom_send_json().await?;
+        assert_eq!(loaders, ["fabric", "forge"]);
+        assert!(matches!(
+            auto.get(missing.clone()).custom_send().await,
+            Err(Error::ReqwestError(err)) if err.status() == Some(reqwest::StatusCode::NOT_FOUND)
+        ));
+        assert_eq!(server.requests.load(Ordering::SeqCst), 2);
+
+        let loaders: Vec<String> = replay.get(url.clone()).custom_send_json().await?;
+        assert_eq!(loaders, ["fabric", "forge"]);
+        // Error responses are replayed too
+        assert!(matches!(
+            replay.get(missing).custom_send().await,
+            Err(Error::ReqwestError(_))
+        ));
+        // Requests with different bodies are different fixtures
+        assert!(matches!(
+            replay.post(url.clone()).json(&["a"]).custom_send().await,
+            Err(Error::MissingFixture(_))
+        ));
+        assert_eq!(server.requests.load(Ordering::SeqCst), 2);
+
+        // Requests with streamed bodies are sent but not recorded
+        let streamed = || reqwest::Body::wrap(String::from("a"));
+        let fixtures = std::fs::read_dir(dir.path())?.count();
+        auto.post(url.clone())
+            .body(streamed())
+            .custom_send()
+            .await?;
+        assert_eq!(server.requests.load(Ordering::SeqCst), 3);
+        assert_eq!(std::fs::read_dir(dir.path())?.count(), fixtures);
+        assert!(matches!(
+            replay.post(url).body(streamed()).custom_send().await,
+            Err(Error::MissingFixture(_))
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,412 @@
+//! Check a directory of mods for updates, and apply them
+
+use crate::{
+    download::{Download, DownloadManager},
+    hashing::hash_path,
+    mrpack::safe_path,
+    structures::version::{HashAlgorithm, LatestVersionBody, Version},
+    Error, Ferinth, Result,
+};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+use tokio::fs;
+
+/// Looks up versions by the SHA512 hashes of their files
+pub(crate) trait HashLookup {
+    /// The versions of the files with `hashes`
+    async fn versions(&self, hashes: Vec<String>) -> Result<HashMap<String, Version>>;
+    /// The latest versions that match `filters` of the projects of the files with `hashes`
+    async fn latest_versions(
+        &self,
+        hashes: Vec<String>,
+        filters: LatestVersionBody,
+    ) -> Result<HashMap<String, Version>>;
+}
+
+impl<T> HashLookup for Ferinth<T> {
+    async fn versions(&self, hashes: Vec<String>) -> Result<HashMap<String, Version>> {
+        self.version_get_from_multiple_hashes(hashes, HashAlgorithm::SHA512)
+            .await
+    }
+
+    async fn latest_versions(
+        &self,
+        hashes: Vec<String>,
+        filters: LatestVersionBody,
+    ) -> Result<HashMap<String, Version>> {
+        self.version_get_latest_from_multiple_hashes(hashes, HashAlgorithm::SHA512, filters)
+            .await
+    }
+}
+
+/// The update status of a local file
+#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
+pub enum FileStatus {
+    /// The file is not known to Modrinth
+    Unknown,
+    /// The file is the latest version that matches the filters,
+    /// or no version of its project matches the filters
+    UpToDate(Version),
+    /// A newer version that matches the filters is available
+    UpdateAvailable { current: Version, latest: Version },
+}
+
+/// The update status of the file at `path`
+#[derive(Debug, Clone)]
+pub struct FileReport {
+    pub path: PathBuf,
+    pub status: FileStatus,
+}
+
+/**
+Checks local files for updates using the version file API calls
+
+## Example
+```no_run
+# use ferinth::{download::DownloadManager, update_checker::{FileStatus, UpdateChecker}};
+# tokio_test::block_on(async {
+# let modrinth = ferinth::Ferinth::default();
+let checker = UpdateChecker::new(vec!["fabric".into()], vec!["1.20.1".into()]);
+let reports = checker.check_dir(&modrinth, "mods").await?;
+for report in &reports {
+    if let FileStatus::UpdateAvailable { current, latest } = &report.status {
+        println!("{}: {} -> {}", report.path.display(), current.version_number, latest.version_number);
+    }
+}
+checker.apply(&modrinth, &reports, &DownloadManager::new(8)).await;
+# Ok::<_, ferinth::Error>(()) }).unwrap()
+```
+*/
+#[derive(Debug, Clone)]
+pub struct UpdateChecker {
+    filters: LatestVersionBody,
+}
+
+impl UpdateChecker {
+    /// Check for updates that support any of the `loaders` and `game_versions`
+    pub fn new(loaders: Vec<String>, game_versions: Vec<String>) -> Self {
+        Self {
+            filters: LatestVersionBody {
+                loaders,
+                game_versions,
+            },
+        }
+    }
+
+    /// Check all the JAR files directly inside `dir` for updates
+    pub async fn check_dir<T>(
+        &self,
+        modrinth: &Ferinth<T>,
+        dir: impl AsRef<Path>,
+    ) -> Result<Vec<FileReport>> {
+        let mut paths = vec![];
+        let mut entries = fs::read_dir(dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if entry.file_type().await?.is_file()
+                && path.extension().is_some_and(|ext| ext == "jar")
+            {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+        self.check_files(modrinth, paths).await
+    }
+
+    /// Check the files at `paths` for updates
+    pub async fn check_files<T>(
+        &self,
+        modrinth: &Ferinth<T>,
+        paths: Vec<PathBuf>,
+    ) -> Result<Vec<FileReport>> {
+        self.check_files_from(modrinth, paths).await
+    }
+
+    async fn check_files_from(
+        &self,
+        source: &impl HashLookup,
+        paths: Vec<PathBuf>,
+    ) -> Result<Vec<FileReport>> {
+        let mut hashes = Vec::with_capacity(paths.len());
+        for path in &paths {
+            hashes.push(hash_path(path).await?.sha512);
+        }
+        if hashes.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let current = source.versions(hashes.clone()).await?;
+        let known = hashes
+            .iter()
+            .filter(|hash| current.contains_key(*hash))
+            .cloned()
+            .collect::<Vec<_>>();
+        let latest = if known.is_empty() {
+            Default::default()
+        } else {
+            source.latest_versions(known, self.filters.clone()).await?
+        };
+
+        Ok(paths
+            .into_iter()
+            .zip(hashes)
+            .map(|(path, hash)| {
+                // Identical files share a hash, so the versions are cloned rather than removed
+                let status = match (current.get(&hash).cloned(), latest.get(&hash).cloned()) {
+                    (None, _) => FileStatus::Unknown,
+                    (Some(current), Some(latest)) if latest.id != current.id => {
+                        FileStatus::UpdateAvailable { current, latest }
+                    }
+                    (Some(current), _) => FileStatus::UpToDate(current),
+                };
+                FileReport { path, status }
+            })
+            .collect())
+    }
+
+    /**
+    Download the primary file of the latest version of every report with an update available
+    into the directory of the outdated file, then delete the outdated file
+
+    Returns the path of the updated file for each report with an update available.
+    Outdated files that update to the same file, such as copies of a mod, share one download.
+    Fails with [`Error::UnsafePath`] for files whose name is not a single path component.
+    */
+    pub async fn apply<T>(
+        &self,
+        modrinth: &Ferinth<T>,
+        reports: &[FileReport],
+        manager: &DownloadManager,
+    ) -> Vec<(PathBuf, Result<PathBuf>)> {
+        let mut updates = vec![];
+        let mut downloads = vec![];
+        let mut indices = HashMap::new();
+        for report in reports {
+            if let FileStatus::UpdateAvailable { latest, .. } = &report.status {
+                let update = latest
+                    .primary_file()
+                    .ok_or(Error::NoVersionFiles)
+                    .and_then(|file| {
+                        let destination = report.path.with_file_name(file_name(&file.filename)?);
+                        let index = *indices.entry(destination.clone()).or_insert_with(|| {
+                            downloads.push(Download::new(file, &destination));
+                            downloads.len() - 1
+                        });
+                        Ok((destination, index))
+                    });
+                updates.push((report.path.clone(), update));
+            }
+        }
+
+        let mut results = manager
+            .download_all(modrinth, downloads)
+            .await
+            .into_iter()
+            .map(Some)
+            .collect::<Vec<_>>();
+        let downloaded = results
+            .iter()
+            .map(|result| matches!(result, Some(Ok(_))))
+            .collect::<Vec<_>>();
+        let mut applied = vec![];
+        for (old, update) in updates {
+            let result = match update {
+                Err(err) => Err(err),
+                Ok((new, index)) if downloaded[index] => {
+                    if old != new {
+                        fs::remove_file(&old)
+                            .await
+                            .map(|()| new)
+                            .map_err(Error::from)
+                    } else {
+                        Ok(new)
+                    }
+                }
+                // Only the first of the files sharing a failed download gets its error
+                Ok((_, index)) => Err(match results[index].take() {
+                    Some(Err(err)) => err,
+                    _ => std::io::Error::other("The shared update failed to download").into(),
+                }),
+            };
+            applied.push((old, result));
+        }
+        applied
+    }
+}
+
+/// Check that `filename` is a single path component, so that it stays in its directory
+fn file_name(filename: &str) -> Result<PathBuf> {
+    let path = safe_path(filename)?;
+    if path.components().count() != 1 {
+        return Err(Error::UnsafePath(filename.to_owned()));
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_server::TestServer,
+        test_versions::{version, TestLookup},
+    };
+    use url::Url;
+
+    fn url(name: &str) -> Url {
+        format!("https://cdn.modrinth.com/{name}").parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn check_files() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let paths = ["a.jar", "a copy.jar", "b.jar", "unknown.jar"]
+            .map(|name| dir.path().join(name))
+            .to_vec();
+        for (path, data) in paths.iter().zip([&b"a1"[..], b"a1", b"b1", b"?"]) {
+            fs::write(path, data).await?;
+        }
+        let lookup = TestLookup(vec![
+            version("aaaaaaa1", "AAAAAAAA")
+                .file("a.jar", b"a1", url("a1"))
+                .build(),
+            version("aaaaaaa2", "AAAAAAAA")
+                .day(2)
+                .file("a.jar", b"a2", url("a2"))
+                .build(),
+            version("bbbbbbb1", "BBBBBBBB")
+                .file("b.jar", b"b1", url("b1"))
+                .build(),
+        ]);
+
+        let reports = UpdateChecker::new(vec![], vec![])
+            .check_files_from(&lookup, paths.clone())
+            .await?;
+        assert_eq!(
+            reports
+                .iter()
+                .map(|report| &report.path)
+                .collect::<Vec<_>>(),
+            paths.iter().collect::<Vec<_>>()
+        );
+        for report in &reports[..2] {
+            assert!(matches!(
+                &report.status,
+                FileStatus::UpdateAvailable { current, latest } if current.id == "aaaaaaa1" && latest.id == "aaaaaaa2"
+            ));
+        }
+        assert!(
+            matches!(&reports[2].status, FileStatus::UpToDate(current) if current.id == "bbbbbbb1")
+        );
+        assert!(matches!(reports[3].status, FileStatus::Unknown));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn apply() -> Result<()> {
+        let server = TestServer::serve(&[("a2.jar", b"a2")]).await;
+        let dir = tempfile::tempdir()?;
+        let (a, b) = (dir.path().join("a1.jar"), dir.path().join("b1.jar"));
+        fs::write(&a, b"a1").await?;
+        fs::write(&b, b"b1").await?;
+
+        let reports = vec![
+            FileReport {
+                path: a.clone(),
+                status: FileStatus::UpdateAvailable {
+                    current: version("aaaaaaa1", "AAAAAAAA").build(),
+                    latest: version("aaaaaaa2", "AAAAAAAA")
+                        .file("a2.jar", b"a2", server.url.join("a2.jar").unwrap())
+                        .build(),
+                },
+            },
+            FileReport {
+                path: b.clone(),
+                status: FileStatus::UpdateAvailable {
+                    current: version("bbbbbbb1", "BBBBBBBB").build(),
+                    // The latest version has no files
+                    latest: version("bbbbbbb2", "BBBBBBBB").build(),
+                },
+            },
+            FileReport {
+                path: dir.path().join("c1.jar"),
+                status: FileStatus::Unknown,
+            },
+        ];
+        let results = UpdateChecker::new(vec![], vec![])
+            .apply(&Ferinth::default(), &reports, &DownloadManager::new(2))
+            .await;
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0, a);
+        assert_eq!(results[0].1.as_ref().unwrap(), &dir.path().join("a2.jar"));
+        assert!(matches!(results[1], (ref path, Err(Error::NoVersionFiles)) if *path == b));
+        assert!(!a.exists());
+        assert!(b.exists());
+        assert_eq!(fs::read(dir.path().join("a2.jar")).await?, b"a2");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn apply_shared_update() -> Result<()> {
+        let server = TestServer::serve(&[("a2.jar", b"a2")]).await;
+        let dir = tempfile::tempdir()?;
+        let paths = ["a.jar", "a copy.jar"].map(|name| dir.path().join(name));
+        let latest = version("aaaaaaa2", "AAAAAAAA")
+            .file("a.jar", b"a2", server.url.join("a2.jar").unwrap())
+            .build();
+        let mut reports = vec![];
+        for path in &paths {
+            fs::write(path, b"a1").await?;
+            reports.push(FileReport {
+                path: path.clone(),
+                status: FileStatus::UpdateAvailable {
+                    current: version("aaaaaaa1", "AAAAAAAA").build(),
+                    latest: latest.clone(),
+                },
+            });
+        }
+
+        let results = UpdateChecker::new(vec![], vec![])
+            .apply(&Ferinth::default(), &reports, &DownloadManager::new(2))
+            .await;
+
+        for (result, path) in results.iter().zip(&paths) {
+            assert_eq!(result.0, *path);
+            assert_eq!(result.1.as_ref().unwrap(), &paths[0]);
+        }
+        assert_eq!(server.requests.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert_eq!(fs::read(&paths[0]).await?, b"a2");
+        assert!(!paths[1].exists());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn apply_unsafe_file_name() -> Result<()> {
+        let server = TestServer::serve(&[("a2.jar", b"a2")]).await;
+        let dir = tempfile::tempdir()?;
+        let mods = dir.path().join("mods");
+        let reports = ["../a2.jar", "/tmp/a2.jar", "sub/a2.jar"].map(|filename| FileReport {
+            path: mods.join("a1.jar"),
+            status: FileStatus::UpdateAvailable {
+                current: version("aaaaaaa1", "AAAAAAAA").build(),
+                latest: version("aaaaaaa2", "AAAAAAAA")
+                    .file(filename, b"a2", server.url.join("a2.jar").unwrap())
+                    .build(),
+            },
+        });
+
+        let results = UpdateChecker::new(vec![], vec![])
+            .apply(&Ferinth::default(), &reports, &DownloadManager::new(2))
+            .await;
+
+        for (_, result) in &results {
+            assert!(matches!(result, Err(Error::UnsafePath(_))));
+        }
+        assert_eq!(server.requests.load(std::sync::atomic::Ordering::SeqCst), 0);
+        assert!(!dir.path().join("a2.jar").exists());
+        Ok(())
+    }
+}
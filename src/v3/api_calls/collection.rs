@@ -0,0 +1,42 @@
+//! API calls related to collections
+//!
+//! [documentation](https://docs.modrinth.com/api/v3/operations/category/collections)
+
+use super::*;
+use structures::collection::*;
+
+impl<T> V3<'_, T> {
+    /// Get the collection of `collection_id`
+    pub async fn collection_get(
+        &self,
+        collection_id: impl IntoId<CollectionId>,
+    ) -> Result<Collection> {
+        let collection_id = collection_id.into_id()?;
+        self.client
+            .get(API_BASE_URL.join_all(vec!["collection", collection_id.as_str()]))
+            .custom_send_json()
+            .await
+    }
+
+    /**
+    Get the collections of the user of `user_id`
+
+    ## Example
+    ```rust
+    # tokio_test::block_on(async {
+    # let modrinth = ferinth::testing::doc_client();
+    let collections = modrinth.v3().user_list_collections("7Azq6eD8").await?;
+    # Ok::<_, ferinth::Error>(()) }).unwrap()
+    ```
+    */
+    pub async fn user_list_collections(
+        &self,
+        user_id: impl IntoId<UserRef>,
+    ) -> Result<Vec<Collection>> {
+        let user_id = user_id.into_id()?;
+        self.client
+            .get(API_BASE_URL.join_all(vec!["user", user_id.as_str(), "collections"]))
+            .custom_send_json()
+            .await
+    }
+}
@@ -0,0 +1,16 @@
+//! [documentation](https://docs.modrinth.com/api/v3)
+
+pub mod collection;
+pub mod organization;
+pub mod project;
+pub mod tag;
+pub mod version;
+
+use super::{structures, API_BASE_URL, V3};
+use crate::{
+    api_calls::into_ids,
+    request::RequestBuilderCustomSend,
+    structures::id::*,
+    url_ext::{UrlJoinAll, UrlWithQuery},
+    Result,
+};
@@ -0,0 +1,76 @@
+//! API calls related to organisations
+//!
+//! [documentation](https://docs.modrinth.com/api/v3/operations/category/organizations)
+
+use super::*;
+use structures::{organization::*, project::Project};
+
+impl<T> V3<'_, T> {
+    /**
+    Get the organisation of `organization_id`
+
+    ## Example
+    ```rust
+    # tokio_test::block_on(async {
+    # let modrinth = ferinth::testing::doc_client();
+    let caffeine_mc = modrinth.v3().organization_get("caffeinemc").await?;
+    assert_eq!(caffeine_mc.name, "CaffeineMC");
+    # Ok::<_, ferinth::Error>(()) }).unwrap()
+    ```
+    */
+    pub async fn organization_get(
+        &self,
+        organization_id: impl IntoId<OrganizationRef>,
+    ) -> Result<Organization> {
+        let organization_id = organization_id.into_id()?;
+        self.client
+            .get(API_BASE_URL.join_all(vec!["organization", organization_id.as_str()]))
+            .custom_send_json()
+            .await
+    }
+
+    /**
+    Get the projects owned by the organisation of `organization_id`
+
+    ## Example
+    ```rust
+    # tokio_test::block_on(async {
+    # let modrinth = ferinth::testing::doc_client();
+    let projects = modrinth.v3().organization_list_projects("caffeinemc").await?;
+    assert!(projects.iter().any(|p| p.id == "AANobbMI"));
+    # Ok::<_, ferinth::Error>(()) }).unwrap()
+    ```
+    */
+    pub async fn organization_list_projects(
+        &self,
+        organization_id: impl IntoId<OrganizationRef>,
+    ) -> Result<Vec<Project>> {
+        let organization_id = organization_id.into_id()?;
+        self.client
+            .get(API_BASE_URL.join_all(vec!["organization", organization_id.as_str(), "projects"]))
+            .custom_send_json()
+            .await
+    }
+
+    /**
+    Get the organisations that the user of `user_id` is a member of
+
+    ## Example
+    ```rust
+    # tokio_test::block_on(async {
+    # let modrinth = ferinth::testing::doc_client();
+    let organizations = modrinth.v3().user_list_organizations("TEZXhE2U").await?;
+    # Ok::<_, ferinth::Error>(()) }).unwrap()
+    ```
+    */
+    pub async fn user_list_organizations(
+        &self,
+        user_id: impl IntoId<UserRef>,
+    ) -> Result<Vec<Organization>> {
+        let user_id = user_id.into_id()?;
+        self.client
+            .get(API_BASE_URL.join_all(vec!["user", user_id.as_str(), "organizations"]))
+            .custom_send_json()
+            .await
+    }
+}
@@ -0,0 +1,55 @@
+//! API calls related to projects
+//!
+//! [documentation](https://docs.modrinth.com/api/v3/operations/category/projects)
+
+use super::*;
+use structures::project::*;
+
+impl<T> V3<'_, T> {
+    /**
+    Get the project of `project_id`
+
+    ## Example
+    ```rust
+    # tokio_test::block_on(async {
+    # let modrinth = ferinth::testing::doc_client();
+    let sodium = modrinth.v3().project_get("AANobbMI").await?;
+    assert_eq!(sodium.name, "Sodium");
+    # Ok::<_, ferinth::Error>(()) }).unwrap()
+    ```
+    */
+    pub async fn project_get(&self, project_id: impl IntoId<ProjectRef>) -> Result<Project> {
+        let project_id = project_id.into_id()?;
+        self.client
+            .get(API_BASE_URL.join_all(vec!["project", project_id.as_str()]))
+            .custom_send_json()
+            .await
+    }
+
+    /**
+    Get the projects of `project_ids`
+
+    ## Example
+    ```rust
+    # tokio_test::block_on(async {
+    # let modrinth = ferinth::testing::doc_client();
+    let projects = modrinth.v3().project_get_multiple(&["sodium", "P7dR8mSH"]).await?;
+    assert_eq!(projects.len(), 2);
+    # Ok::<_, ferinth::Error>(()) }).unwrap()
+    ```
+    */
+    pub async fn project_get_multiple(
+        &self,
+        project_ids: &[impl IntoId<ProjectRef> + Clone],
+    ) -> Result<Vec<Project>> {
+        let project_ids = into_ids(project_ids)?;
+        self.client
+            .get(
+                API_BASE_URL
+                    .join_all(vec!["projects"])
+                    .with_query_json("ids", project_ids)?,
+            )
+            .custom_send_json()
+            .await
+    }
+}
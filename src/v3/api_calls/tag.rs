@@ -0,0 +1,83 @@
+//! API calls related to tags
+//!
+//! [documentation](https://docs.modrinth.com/api/v3/operations/category/tags)
+
+use super::*;
+use structures::loader_fields::*;
+
+impl<T> V3<'_, T> {
+    /**
+    List the loaders, their icons, supported project types, and the loader fields their versions can have
+
+    ## Example
+    ```rust
+    # tokio_test::block_on(async {
+    # let modrinth = ferinth::testing::doc_client();
+    let loaders = modrinth.v3().tag_list_loaders().await?;
+    let fabric = loaders.iter().find(|l| l.name == "fabric").unwrap();
+    assert!(fabric.supported_fields.contains(&"game_versions".to_string()));
+    # Ok::<_, ferinth::Error>(()) }).unwrap()
+    ```
+    */
+    pub async fn tag_list_loaders(&self) -> Result<Vec<Loader>> {
+        self.client
+            .get(API_BASE_URL.join_all(vec!["tag", "loader"]))
+            .custom_send_json()
+            .await
+    }
+
+    /**
+    List the allowed values of the enum loader field of `name`
+
+    ## Example
+    ```rust
+    # tokio_test::block_on(async {
+    # let modrinth = ferinth::testing::doc_client();
+    let mrpack_loaders = modrinth.v3().tag_list_loader_field_values("mrpack_loaders").await?;
+    assert!(mrpack_loaders.iter().any(|v| v.value == "fabric"));
+    # Ok::<_, ferinth::Error>(()) }).unwrap()
+    ```
+    */
+    pub async fn tag_list_loader_field_values(
+        &self,
+        name: &str,
+    ) -> Result<Vec<LoaderFieldEnumValue>> {
+        self.client
+            .get(
+                API_BASE_URL
+                    .join_all(vec!["loader_field"])
+                    .with_query("loader_field", name),
+            )
+            .custom_send_json()
+            .await
+    }
+
+    /**
+    Check that the values of the enum loader fields of `names` in `fields` are allowed,
+    so that they can be uploaded without being rejected
+
+    ## Example
+    ```rust
+    # use ferinth::v3::structures::loader_fields::LoaderFields;
+    # tokio_test::block_on(async {
+    # let modrinth = ferinth::testing::doc_client();
+    let fields = LoaderFields {
+        game_versions: vec!["1.20.1".into()],
+        mrpack_loaders: Some(vec!["fabric".into()]),
+        ..Default::default()
+    };
+    modrinth.v3().tag_validate_loader_fields(&fields, &["game_versions", "mrpack_loaders"]).await?;
+    # Ok::<_, ferinth::Error>(()) }).unwrap()
+    ```
+    */
+    pub async fn tag_validate_loader_fields(
+        &self,
+        fields: &LoaderFields,
+        names: &[&str],
+    ) -> Result<()> {
+        for name in names {
+            fields.validate(name, &self.tag_list_loader_field_values(name).await?)?;
+        }
+        Ok(())
+    }
+}
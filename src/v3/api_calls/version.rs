@@ -0,0 +1,76 @@
+//! API calls related to versions
+//!
+//! [documentation](https://docs.modrinth.com/api/v3/operations/category/versions)
+
+use super::*;
+use structures::version::*;
+
+impl<T> V3<'_, T> {
+    /**
+    Get the versions of the project of `project_id`
+
+    ## Example
+    ```rust
+    # tokio_test::block_on(async {
+    # let modrinth = ferinth::testing::doc_client();
+    let sodium_versions = modrinth.v3().version_list("AANobbMI").await?;
+    sodium_versions.iter().for_each(|v| assert_eq!(v.project_id, "AANobbMI"));
+    # Ok::<_, ferinth::Error>(()) }).unwrap()
+    ```
+    */
+    pub async fn version_list(&self, project_id: impl IntoId<ProjectRef>) -> Result<Vec<Version>> {
+        let project_id = project_id.into_id()?;
+        self.client
+            .get(API_BASE_URL.join_all(vec!["project", project_id.as_str(), "version"]))
+            .custom_send_json()
+            .await
+    }
+
+    /**
+    Get the version of `version_id`
+
+    ## Example
+    ```rust
+    # tokio_test::block_on(async {
+    # let modrinth = ferinth::testing::doc_client();
+    let sodium_version = modrinth.v3().version_get("xuWxRZPd").await?;
+    assert_eq!(sodium_version.project_id, "AANobbMI");
+    assert!(sodium_version.fields.game_versions.contains(&"1.17.1".to_string()));
+    # Ok::<_, ferinth::Error>(()) }).unwrap()
+    ```
+    */
+    pub async fn version_get(&self, version_id: impl IntoId<VersionId>) -> Result<Version> {
+        let version_id = version_id.into_id()?;
+        self.client
+            .get(API_BASE_URL.join_all(vec!["version", version_id.as_str()]))
+            .custom_send_json()
+            .await
+    }
+
+    /**
+    Get the versions of `version_ids`
+
+    ## Example
+    ```rust
+    # tokio_test::block_on(async {
+    # let modrinth = ferinth::testing::doc_client();
+    let versions = modrinth.v3().version_get_multiple(&["sxWTUZpD", "mgPpe4NY"]).await?;
+    versions.iter().for_each(|v| assert_eq!(v.project_id, "of7wIinq"));
+    # Ok::<_, ferinth::Error>(()) }).unwrap()
+    ```
+    */
+    pub async fn version_get_multiple(
+        &self,
+        version_ids: &[impl IntoId<VersionId> + Clone],
+    ) -> Result<Vec<Version>> {
+        let version_ids = into_ids(version_ids)?;
+        self.client
+            .get(
+                API_BASE_URL
+                    .join_all(vec!["versions"])
+                    .with_query_json("ids", version_ids)?,
+            )
+            .custom_send_json()
+            .await
+    }
+}
@@ -0,0 +1,43 @@
+/*!
+Bindings for version 3 of the Modrinth API
+
+v3 exposes data that v2 does not, such as loader fields, organisations, and collections.
+It is not yet stable, so its routes and models may change without a major version bump of this crate.
+
+The API calls are accessed through [`Ferinth::v3`], which shares the client and authentication of the container.
+
+```rust
+# tokio_test::block_on(async {
+# let modrinth = ferinth::testing::doc_client();
+let sodium = modrinth.v3().project_get("sodium").await?;
+assert_eq!(sodium.name, "Sodium");
+# Ok::<_, ferinth::Error>(()) }).unwrap()
+```
+*/
+
+mod api_calls;
+pub mod structures;
+
+use crate::{request::Client, Ferinth, BASE_URL};
+use std::{marker::PhantomData, sync::LazyLock};
+use url::Url;
+
+/// The base URL for version 3 of the Modrinth API
+pub static API_BASE_URL: LazyLock<Url> =
+    LazyLock::new(|| BASE_URL.join("v3/").expect("Invalid API base URL"));
+
+/// A view of a [`Ferinth`] container that invokes version 3 API calls
+pub struct V3<'a, Auth> {
+    client: &'a Client,
+    auth: PhantomData<Auth>,
+}
+
+impl<Auth> Ferinth<Auth> {
+    /// Invoke version 3 API calls using this container's client and authentication
+    pub fn v3(&self) -> V3<'_, Auth> {
+        V3 {
+            client: &self.client,
+            auth: PhantomData,
+        }
+    }
+}
@@ -0,0 +1,35 @@
+//! Models related to collections
+//!
+//! [documentation](https://docs.modrinth.com/api/v3/operations/getcollection)
+
+use super::*;
+
+/// A list of projects curated by a user
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Collection {
+    pub id: CollectionId,
+    /// The ID of the user who owns this collection
+    pub user: UserId,
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(deserialize_with = "deserialise_optional_url")]
+    pub icon_url: Option<Url>,
+    /// The RGB color of the collection, automatically generated from the icon
+    pub color: Option<Int>,
+    pub status: CollectionStatus,
+    pub created: UtcTime,
+    pub updated: UtcTime,
+    /// The IDs of the projects in this collection
+    pub projects: Vec<ProjectId>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CollectionStatus {
+    Listed,
+    Unlisted,
+    Private,
+    Rejected,
+    #[serde(other)]
+    Unknown,
+}
@@ -0,0 +1,179 @@
+//! Models related to loader fields
+//!
+//! Loader fields are version metadata that depends on the loaders of the version,
+//! such as the game versions a mod supports, or which environments a mod runs in.
+
+use super::*;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// The loader fields of a version
+///
+/// Fields that are not known to this crate are available in [`Self::others`].
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+pub struct LoaderFields {
+    /// The game versions this version supports
+    #[serde(default)]
+    pub game_versions: Vec<String>,
+    /// The loaders that a modpack version is made for
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mrpack_loaders: Option<Vec<String>>,
+    /// The environments this version runs in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub environment: Option<Environment>,
+    /// Whether this version only runs on the client
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_only: Option<bool>,
+    /// Whether this version only runs on the server
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_only: Option<bool>,
+    /// Whether this version has to be installed on both the client and the server
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_and_server: Option<bool>,
+    /// Whether this version runs in singleplayer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub singleplayer: Option<bool>,
+    /// Other loader fields, keyed by the field's name
+    #[serde(flatten)]
+    pub others: HashMap<String, Value>,
+}
+
+impl LoaderFields {
+    /// Deserialise the value of the unknown field of `name`, if it is present
+    pub fn get<T: DeserializeOwned>(&self, name: &str) -> Option<serde_json::Result<T>> {
+        self.others
+            .get(name)
+            .map(|value| T::deserialize(value.clone()))
+    }
+
+    /// The string values of the field of `name`, whether it holds one value or an array of them
+    pub fn values_of(&self, name: &str) -> Vec<String> {
+        match serde_json::to_value(self) {
+            Ok(Value::Object(mut fields)) => match fields.remove(name) {
+                Some(Value::String(value)) => vec![value],
+                Some(Value::Array(values)) => values
+                    .into_iter()
+                    .filter_map(|value| match value {
+                        Value::String(value) => Some(value),
+                        _ => None,
+                    })
+                    .collect(),
+                _ => vec![],
+            },
+            _ => vec![],
+        }
+    }
+
+    /// Check that the values of the enum field of `name` are all in `allowed`
+    pub fn validate(&self, name: &str, allowed: &[LoaderFieldEnumValue]) -> crate::Result<()> {
+        match self
+            .values_of(name)
+            .into_iter()
+            .find(|value| !allowed.iter().any(|allowed| &allowed.value == value))
+        {
+            Some(value) => Err(crate::Error::InvalidLoaderFieldValue {
+                field: name.to_owned(),
+                value,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// An allowed value of an enum loader field
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct LoaderFieldEnumValue {
+    pub value: String,
+    /// The order in which the values should be displayed
+    pub ordering: Option<isize>,
+    pub created: UtcTime,
+    /// Extra data about the value, e.g. the release type of a game version
+    pub metadata: Value,
+}
+
+/// A loader and the fields that versions using it can have
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Loader {
+    /// An SVG icon for the loader
+    pub icon: String,
+    pub name: String,
+    /// The project types that this loader can load
+    pub supported_project_types: Vec<String>,
+    /// The games that this loader supports
+    pub supported_games: Vec<String>,
+    /// The names of the loader fields that versions using this loader can have
+    pub supported_fields: Vec<String>,
+    pub metadata: Value,
+}
+
+/// The environments a version can run in
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Environment {
+    /// Required on both the client and the server
+    ClientAndServer,
+    /// Only runs on the client
+    ClientOnly,
+    /// Only runs on the client, but can optionally be installed on the server too
+    ClientOnlyServerOptional,
+    /// Only runs in singleplayer
+    SingleplayerOnly,
+    /// Only runs on the server
+    ServerOnly,
+    /// Only runs on the server, but can optionally be installed on the client too
+    ServerOnlyClientOptional,
+    /// Only runs on dedicated servers
+    DedicatedServerOnly,
+    /// Runs on either the client or the server, or both
+    ClientOrServer,
+    /// Runs on either the client or the server, but works best on both
+    ClientOrServerPrefersBoth,
+    #[serde(other)]
+    Unknown,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_and_unknown_fields() {
+        let fields: LoaderFields = serde_json::from_value(serde_json::json!({
+            "game_versions": ["1.20.1", "1.20.2"],
+            "mrpack_loaders": ["fabric"],
+            "client_only": false,
+            "singleplayer": true,
+            "environment": "client_and_server",
+            "custom_field": 3,
+        }))
+        .unwrap();
+        assert_eq!(fields.game_versions, ["1.20.1", "1.20.2"]);
+        assert_eq!(fields.client_only, Some(false));
+        assert_eq!(fields.environment, Some(Environment::ClientAndServer));
+        assert_eq!(fields.get::<u8>("custom_field").unwrap().unwrap(), 3);
+        assert_eq!(fields.values_of("mrpack_loaders"), ["fabric"]);
+        assert_eq!(fields.values_of("environment"), ["client_and_server"]);
+    }
+
+    #[test]
+    fn validate_enum_values() {
+        let allowed = ["fabric", "quilt"].map(|value| LoaderFieldEnumValue {
+            value: value.to_owned(),
+            ordering: None,
+            created: chrono::DateTime::UNIX_EPOCH,
+            metadata: Value::Null,
+        });
+        let mut fields = LoaderFields {
+            mrpack_loaders: Some(vec!["quilt".into()]),
+            ..Default::default()
+        };
+        assert!(fields.validate("mrpack_loaders", &allowed).is_ok());
+
+        fields.mrpack_loaders = Some(vec!["fabric".into(), "forge".into()]);
+        assert!(matches!(
+            fields.validate("mrpack_loaders", &allowed),
+            Err(crate::Error::InvalidLoaderFieldValue { value, .. }) if value == "forge"
+        ));
+    }
+}
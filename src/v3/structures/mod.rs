@@ -0,0 +1,15 @@
+//! Models specified in the Modrinth v3 documentation
+//!
+//! Models that have not changed since v2 are reused from [`crate::structures`].
+
+pub mod collection;
+pub mod loader_fields;
+pub mod organization;
+pub mod project;
+pub mod version;
+
+pub use crate::structures::{id::*, Int, UtcTime, ID};
+
+use crate::structures::deserialise_optional_url;
+use serde::{Deserialize, Serialize};
+use url::Url;
@@ -0,0 +1,24 @@
+//! Models related to organisations
+//!
+//! [documentation](https://docs.modrinth.com/api/v3/operations/getorganization)
+
+use super::*;
+pub use crate::structures::user::TeamMember;
+
+/// A group of users that owns projects together
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Organization {
+    pub id: OrganizationId,
+    /// The organisation's slug, used for vanity URLs.
+    /// This can change at any time, so use the [`Self::id`] for long term storage.
+    pub slug: String,
+    pub name: String,
+    /// The ID of the team of the organisation's members
+    pub team_id: TeamId,
+    pub description: String,
+    #[serde(deserialize_with = "deserialise_optional_url")]
+    pub icon_url: Option<Url>,
+    /// The RGB color of the organisation, automatically generated from the icon
+    pub color: Option<Int>,
+    pub members: Vec<TeamMember>,
+}
@@ -0,0 +1,151 @@
+//! Models related to projects
+//!
+//! [documentation](https://docs.modrinth.com/api/v3/operations/getproject)
+
+use super::*;
+pub use crate::structures::project::{License, MonetizationStatus, ProjectStatus};
+use std::collections::HashMap;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Project {
+    pub id: ProjectId,
+    /// The project's slug, used for vanity URLs.
+    /// This can change at any time, so use the [`Self::id`] for long term storage.
+    pub slug: Option<String>,
+    /// The types of the versions of this project, such as `mod` and `plugin`
+    pub project_types: Vec<String>,
+    /// The games that the versions of this project support
+    pub games: Vec<String>,
+    /// The ID of the team that has ownership of this project
+    pub team_id: TeamId,
+    /// The ID of the organisation that owns this project
+    pub organization: Option<OrganizationId>,
+    pub name: String,
+    /// A short description of the project
+    pub summary: String,
+    /// A long form description of the project
+    pub description: String,
+    pub published: UtcTime,
+    pub updated: UtcTime,
+    /// The date the project's status was approved
+    pub approved: Option<UtcTime>,
+    pub queued: Option<UtcTime>,
+    pub status: ProjectStatus,
+    /// The status requested. Only visible to those with appropriate permissions
+    pub requested_status: Option<ProjectStatus>,
+    pub license: License,
+    pub downloads: Int,
+    pub followers: Int,
+    pub categories: Vec<String>,
+    /// A list of categories which are searchable but non-primary
+    pub additional_categories: Vec<String>,
+    /// A list of all of the loaders supported by the project
+    pub loaders: Vec<String>,
+    /// A list of the version IDs of the project.
+    /// This will only ever be empty if the project is a draft.
+    pub versions: Vec<VersionId>,
+    #[serde(deserialize_with = "deserialise_optional_url")]
+    pub icon_url: Option<Url>,
+    /// Links to the project's source code, issue tracker, donation pages, etc., keyed by the platform's ID
+    pub link_urls: HashMap<String, Link>,
+    /// A list of images that have been uploaded to the project's gallery
+    pub gallery: Vec<GalleryItem>,
+    /// The RGB color of the project, automatically generated from the project icon
+    pub color: Option<Int>,
+    /// The ID of the moderation thread associated with this project
+    pub thread_id: ThreadId,
+    pub monetization_status: MonetizationStatus,
+    /// Other fields, keyed by their name.
+    /// These include the values of the loader fields of all of the project's versions, as arrays.
+    #[serde(flatten)]
+    pub fields: HashMap<String, serde_json::Value>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Link {
+    /// The ID of the platform the link points to
+    pub platform: String,
+    /// Whether this link is for donating to the project
+    pub donation: bool,
+    pub url: Url,
+}
+
+/// An image that have been uploaded to a project's gallery
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GalleryItem {
+    pub url: Url,
+    pub raw_url: Url,
+    pub featured: bool,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub created: UtcTime,
+    /// The order of the gallery image.
+    /// Gallery images are sorted by this field and then alphabetically by name.
+    pub ordering: isize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialise() {
+        let project: Project = serde_json::from_value(serde_json::json!({
+            "id": "AANobbMI",
+            "slug": "sodium",
+            "project_types": ["mod"],
+            "games": ["minecraft-java"],
+            "team_id": "4reLOAKe",
+            "organization": "BZoBsPo6",
+            "name": "Sodium",
+            "summary": "The fastest and most compatible rendering optimization mod for Minecraft.",
+            "description": "",
+            "published": "2021-01-03T00:53:34.185936Z",
+            "updated": "2024-06-11T16:26:48.539414Z",
+            "approved": "2021-01-03T00:53:34.185936Z",
+            "queued": null,
+            "status": "approved",
+            "requested_status": "approved",
+            "moderator_message": null,
+            "license": {
+                "id": "LicenseRef-Polyform-Shield-License-1.0.0",
+                "name": "",
+                "url": "https://polyformproject.org/licenses/shield/1.0.0/",
+            },
+            "downloads": 43000000,
+            "followers": 24000,
+            "categories": ["optimization"],
+            "additional_categories": [],
+            "loaders": ["fabric", "neoforge", "quilt"],
+            "versions": ["yaoBL9D9", "ZuPQBdna"],
+            "icon_url": "",
+            "link_urls": {
+                "source": {
+                    "platform": "source",
+                    "donation": false,
+                    "url": "https://github.com/CaffeineMC/sodium",
+                },
+            },
+            "gallery": [],
+            "color": 8703084,
+            "thread_id": "AANobbMI",
+            "monetization_status": "monetized",
+            "side_types_migration_review_status": "reviewed",
+            "game_versions": ["1.20.6", "1.21"],
+            "environment": ["client_only"],
+            "client_only": [true],
+        }))
+        .unwrap();
+        assert_eq!(project.icon_url, None);
+        assert_eq!(project.link_urls["source"].platform, "source");
+        assert_eq!(
+            project.fields["game_versions"],
+            serde_json::json!(["1.20.6", "1.21"])
+        );
+        assert_eq!(project.fields["moderator_message"], serde_json::Value::Null);
+        assert_eq!(
+            project.fields["side_types_migration_review_status"],
+            "reviewed"
+        );
+    }
+}
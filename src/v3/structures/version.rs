@@ -0,0 +1,92 @@
+//! Models related to versions
+//!
+//! [documentation](https://docs.modrinth.com/api/v3/operations/getversion)
+
+use super::*;
+pub use crate::structures::version::{Dependency, Status, VersionFile, VersionType};
+use loader_fields::LoaderFields;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Version {
+    pub id: VersionId,
+    pub project_id: ProjectId,
+    /// The user ID of the author who published this version
+    pub author_id: UserId,
+    pub featured: bool,
+    pub name: String,
+    /// Ideally will follow semantic versioning
+    pub version_number: String,
+    /// The types of this version's project, such as `mod` and `plugin`
+    pub project_types: Vec<String>,
+    /// The games that this version supports
+    pub games: Vec<String>,
+    pub changelog: String,
+    pub date_published: UtcTime,
+    pub downloads: Int,
+    /// The release channel for this version
+    pub version_type: VersionType,
+    pub status: Status,
+    pub requested_status: Option<Status>,
+    /// A list of files available for download
+    pub files: Vec<VersionFile>,
+    pub dependencies: Vec<Dependency>,
+    pub loaders: Vec<String>,
+    /// The order of the version, overriding the publish date when sorting
+    pub ordering: Option<isize>,
+    /// The fields specific to this version's loaders, such as the supported game versions
+    #[serde(flatten)]
+    pub fields: LoaderFields,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use loader_fields::Environment;
+
+    #[test]
+    fn deserialise() {
+        let version: Version = serde_json::from_value(serde_json::json!({
+            "id": "yaoBL9D9",
+            "project_id": "AANobbMI",
+            "author_id": "DzLrfrbK",
+            "featured": false,
+            "name": "Sodium 0.5.8 for Fabric 1.20.4",
+            "version_number": "mc1.20.4-0.5.8",
+            "project_types": ["mod"],
+            "games": ["minecraft-java"],
+            "changelog": "",
+            "date_published": "2024-02-16T00:54:07.412591Z",
+            "downloads": 1200000,
+            "version_type": "release",
+            "status": "listed",
+            "requested_status": null,
+            "files": [{
+                "hashes": { "sha512": "a", "sha1": "b" },
+                "url": "https://cdn.modrinth.com/data/AANobbMI/versions/yaoBL9D9/sodium-fabric-0.5.8%2Bmc1.20.4.jar",
+                "filename": "sodium-fabric-0.5.8+mc1.20.4.jar",
+                "primary": true,
+                "size": 1000000,
+                "file_type": null,
+            }],
+            "dependencies": [],
+            "loaders": ["fabric"],
+            "ordering": null,
+            "game_versions": ["1.20.3", "1.20.4"],
+            "environment": "client_only",
+            "client_only": true,
+            "client_and_server": false,
+            "server_only": false,
+            "singleplayer": true,
+            "mrpack_loaders": null,
+        }))
+        .unwrap();
+        assert_eq!(version.fields.game_versions, ["1.20.3", "1.20.4"]);
+        assert_eq!(version.fields.environment, Some(Environment::ClientOnly));
+        assert_eq!(version.fields.client_only, Some(true));
+        assert_eq!(version.fields.mrpack_loaders, None);
+        assert_eq!(
+            version.files[0].filename,
+            "sodium-fabric-0.5.8+mc1.20.4.jar"
+        );
+    }
+}